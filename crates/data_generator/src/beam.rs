@@ -0,0 +1,97 @@
+use crate::{
+    cli::{BeamArgs, Cli, EvaluatorKind},
+    policy::build_policy,
+    prefix::{format_colors, write_colorings},
+};
+use clap::{CommandFactory, error::ErrorKind};
+#[cfg(feature = "onnx")]
+use ramsey_theory::policies::onnx::OnnxPolicy;
+use ramsey_theory::{
+    Evaluator, SequenceProblem,
+    assert_const_generics::*,
+    search::{PossibleCapacity, Rollout, beam::BeamSearch},
+};
+use rand::{SeedableRng, rngs::StdRng};
+
+// Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+#[allow(clippy::eq_op)]
+pub fn beam<P>(args: BeamArgs)
+where
+    P: SequenceProblem,
+    Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    if args.width == 0 {
+        Cli::command()
+            .error(ErrorKind::ValueValidation, "the width must be positive")
+            .exit();
+    }
+    if args.rollouts == 0 {
+        Cli::command()
+            .error(ErrorKind::ValueValidation, "at least one rollout is needed")
+            .exit();
+    }
+
+    let policy = build_policy::<P>(&args.policy);
+    let evaluator: Box<dyn Evaluator<P>> = match args.evaluator {
+        EvaluatorKind::Possible => Box::new(PossibleCapacity),
+        EvaluatorKind::Rollout => Box::new(Rollout {
+            policy: policy.as_ref(),
+            n_rollouts: args.rollouts,
+        }),
+        #[cfg(feature = "onnx")]
+        EvaluatorKind::Onnx => {
+            // Required by clap with the onnx evaluator.
+            let model = args.value_model.as_deref().unwrap();
+
+            let model = OnnxPolicy::load::<P>(model).unwrap_or_else(|err| {
+                let message = format!("cannot load the ONNX model: {err:#}");
+                Cli::command()
+                    .error(ErrorKind::InvalidValue, message)
+                    .exit()
+            });
+            if !model.has_values() {
+                Cli::command()
+                    .error(
+                        ErrorKind::InvalidValue,
+                        "the ONNX model has no value output",
+                    )
+                    .exit();
+            }
+            Box::new(model)
+        }
+    };
+
+    let search = BeamSearch {
+        evaluator: evaluator.as_ref(),
+        width: args.width,
+    };
+    let mut rng = StdRng::seed_from_u64(args.seed);
+    let result = search.search::<P>(&mut rng);
+
+    println!("depth candidates duplicates  kept   best   mean");
+    for stats in &result.stats {
+        println!(
+            "{:>5} {:>10} {:>10} {:>5} {:>6.3} {:>6.3}",
+            stats.depth,
+            stats.candidates,
+            stats.duplicates,
+            stats.kept,
+            stats.best_value,
+            stats.mean_value,
+        );
+    }
+
+    println!(
+        "best coloring of size {} (upper bound {}): {}",
+        result.best.len(),
+        P::BOUND - 1,
+        format_colors(&result.best)
+    );
+
+    if let Some(filename) = &args.output_file {
+        write_colorings(filename, &[&result.best])
+            .unwrap_or_else(|err| Cli::command().error(ErrorKind::Io, err).exit());
+    }
+}
@@ -1,14 +1,66 @@
-use clap::{Parser, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum, builder::RangedU64ValueParser};
 use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(version, about)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Cli {
-    #[arg(short, long, value_enum)]
-    pub problem: Problem,
+    #[command(flatten)]
+    pub generate: GenerateArgs,
 
-    #[arg(short, long)]
-    pub colors: usize,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Play games with a policy and report their final sizes
+    Evaluate(EvaluateArgs),
+    /// Run a beam search and report the best coloring and statistics of each depth
+    Beam(BeamArgs),
+    /// Anneal full colorings of increasing sizes down to no monochromatic solution
+    LocalSearch(LocalSearchArgs),
+    /// Evolve a population of colorings and write the best ones
+    Genetic(GeneticArgs),
+    /// Write the DIMACS CNF of the colorings of `1..=n` without monochromatic solution
+    Cnf(CnfArgs),
+    /// Verify the model of a SAT solver on the CNF of `cnf` and save its coloring
+    Import(ImportArgs),
+    /// Find with the built-in SAT solver a coloring of `1..=n`, or prove there is none
+    #[cfg(feature = "sat")]
+    Solve(SolveArgs),
+    /// Compare the pruning of the layers of symmetry breaking on the DFS and the CNF of a size
+    Symmetry(SymmetryArgs),
+}
+
+fn probability(value: &str) -> Result<f64, String> {
+    let probability: f64 = value.parse().map_err(|err| format!("{err}"))?;
+
+    if (0.0..=1.0).contains(&probability) {
+        Ok(probability)
+    } else {
+        Err(format!("{probability} is not in [0, 1]"))
+    }
+}
+
+fn temperature(value: &str) -> Result<f64, String> {
+    let temperature: f64 = value.parse().map_err(|err| format!("{err}"))?;
+
+    if temperature.is_finite() && temperature >= 0.0 {
+        Ok(temperature)
+    } else {
+        Err(format!("{temperature} is not a finite non-negative number"))
+    }
+}
+
+#[derive(Args)]
+pub struct GenerateArgs {
+    // Optional only so that the subcommands can be parsed without them.
+    #[arg(short, long, value_enum, required = true)]
+    pub problem: Option<Problem>,
+
+    #[arg(short, long, required = true)]
+    pub colors: Option<usize>,
 
     #[arg(short, long)]
     #[arg(default_value_t = 100_000)]
@@ -23,6 +75,495 @@ pub struct Cli {
     #[arg(long)]
     #[arg(default_value_t = 500)]
     pub chunk_size: usize,
+
+    /// Relabel the colors in order of first appearance before deduplication
+    #[arg(long)]
+    pub canonical: bool,
+
+    /// Emit this many random color permutations of each sample
+    #[arg(long, value_name = "K", conflicts_with = "canonical")]
+    #[arg(value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub augment: Option<usize>,
+
+    #[arg(short, long, value_enum)]
+    #[arg(default_value_t = Mode::Partial)]
+    pub mode: Mode,
+
+    /// Drop the duplicated colorings, the default in partial mode
+    #[arg(long, overrides_with = "no_dedup")]
+    pub dedup: bool,
+
+    /// Keep the duplicated colorings, the default in trajectory mode to keep whole games and in
+    /// complete mode to measure the final sizes of random play
+    #[arg(long, overrides_with = "dedup")]
+    pub no_dedup: bool,
+
+    /// Fill a quota of samples for each size instead of a total number of samples
+    #[arg(long, value_enum)]
+    pub size_distribution: Option<SizeDistribution>,
+
+    /// File of `size weight` lines used by `--size-distribution custom`
+    #[arg(long, required_if_eq("size_distribution", "custom"))]
+    pub size_weights: Option<PathBuf>,
+
+    #[arg(long, requires = "size_distribution")]
+    pub min_size: Option<usize>,
+
+    #[arg(long, requires = "size_distribution")]
+    pub max_size: Option<usize>,
+
+    /// Number of games without a new sample after which a size is considered saturated
+    #[arg(long)]
+    #[arg(default_value_t = 10_000)]
+    pub saturation_patience: usize,
+
+    /// Flag the samples whose game reaches a dead end within this many moves
+    #[arg(long, value_name = "D")]
+    pub dead_end_depth: Option<usize>,
+
+    /// Probability of truncating a game within `--dead-end-depth` moves of its dead end, in
+    /// partial mode
+    #[arg(long, requires = "dead_end_depth", value_parser = probability)]
+    #[arg(conflicts_with = "size_distribution")]
+    pub dead_end_oversampling: Option<f64>,
+
+    /// Comma separated colors every game starts from
+    #[arg(long, value_delimiter = ',')]
+    pub prefix: Option<Vec<usize>>,
+
+    /// File containing the colors every game starts from
+    #[arg(long, conflicts_with = "prefix")]
+    pub prefix_file: Option<PathBuf>,
+
+    /// File of colorings, one per line, mutated to start the games
+    #[arg(long, conflicts_with_all = ["prefix", "prefix_file"])]
+    pub seeds: Option<PathBuf>,
+
+    /// Number of colors changed in a truncated seed
+    #[arg(long, requires = "seeds")]
+    #[arg(default_value_t = 1)]
+    pub mutations: usize,
+
+    /// Also write the observation tensors of `ramsey_theory::observation`
+    #[arg(long)]
+    pub observations: bool,
+
+    #[command(flatten)]
+    pub policy: PolicyArgs,
+}
+
+#[derive(Args)]
+pub struct EvaluateArgs {
+    #[arg(short, long, value_enum)]
+    pub problem: Problem,
+
+    #[arg(short, long)]
+    pub colors: usize,
+
+    #[arg(short, long)]
+    #[arg(default_value_t = 1_000)]
+    pub games: usize,
+
+    /// Seed of the first game, the following games use the next seeds
+    #[arg(long)]
+    #[arg(default_value_t = 0)]
+    pub seed: u64,
+
+    #[arg(short, long)]
+    pub workers: Option<usize>,
+
+    #[command(flatten)]
+    pub policy: PolicyArgs,
+}
+
+#[derive(Args)]
+pub struct BeamArgs {
+    #[arg(short, long, value_enum)]
+    pub problem: Problem,
+
+    #[arg(short, long)]
+    pub colors: usize,
+
+    /// Number of colorings kept at each depth
+    #[arg(long)]
+    #[arg(default_value_t = 100)]
+    pub width: usize,
+
+    #[arg(short, long, value_enum)]
+    #[arg(default_value_t = EvaluatorKind::Possible)]
+    pub evaluator: EvaluatorKind,
+
+    /// Games played by the policy to evaluate each coloring with the rollout evaluator
+    #[arg(long)]
+    #[arg(default_value_t = 1)]
+    pub rollouts: usize,
+
+    /// ONNX model with a value output, for the onnx evaluator
+    #[cfg(feature = "onnx")]
+    #[arg(long, required_if_eq("evaluator", "onnx"))]
+    pub value_model: Option<PathBuf>,
+
+    #[arg(long)]
+    #[arg(default_value_t = 0)]
+    pub seed: u64,
+
+    /// Append the best coloring there, as comma-separated colors
+    #[arg(short, long)]
+    pub output_file: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub policy: PolicyArgs,
+}
+
+#[derive(Args)]
+pub struct LocalSearchArgs {
+    #[arg(short, long, value_enum)]
+    pub problem: Problem,
+
+    #[arg(short, long)]
+    pub colors: usize,
+
+    /// First size searched, the next sizes start from the previous solution
+    #[arg(short = 'n', long)]
+    #[arg(default_value_t = 1)]
+    pub size: usize,
+
+    /// Last size searched, the upper bound by default
+    #[arg(long)]
+    pub max_size: Option<usize>,
+
+    /// Recolorings tried for each size
+    #[arg(long)]
+    #[arg(default_value_t = 1_000_000)]
+    pub steps: usize,
+
+    /// Initial temperature of the annealing, 0 only accepts the moves not increasing the energy
+    #[arg(long, value_parser = temperature)]
+    #[arg(default_value_t = 1.0)]
+    pub temperature: f64,
+
+    /// Factor of the temperature after each step
+    #[arg(long)]
+    #[arg(default_value_t = 0.99999)]
+    pub cooling: f64,
+
+    /// Steps during which a number cannot get back its previous color
+    #[arg(long)]
+    #[arg(default_value_t = 10)]
+    pub tabu_tenure: usize,
+
+    #[arg(long)]
+    #[arg(default_value_t = 0)]
+    pub seed: u64,
+
+    /// Append the largest coloring found there, as comma-separated colors
+    #[arg(short, long)]
+    pub output_file: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct GeneticArgs {
+    #[arg(short, long, value_enum)]
+    pub problem: Problem,
+
+    #[arg(short, long)]
+    pub colors: usize,
+
+    #[arg(long)]
+    #[arg(default_value_t = 200)]
+    pub population: usize,
+
+    #[arg(short, long)]
+    #[arg(default_value_t = 100)]
+    pub generations: usize,
+
+    #[arg(short, long, value_enum)]
+    #[arg(default_value_t = Fitness::Prefix)]
+    pub fitness: Fitness,
+
+    /// Size of the colorings with the violations fitness
+    #[arg(short = 'n', long, required_if_eq("fitness", "violations"))]
+    pub size: Option<usize>,
+
+    /// Number of individuals competing to be a parent
+    #[arg(long)]
+    #[arg(default_value_t = 3)]
+    pub tournament: usize,
+
+    /// Probability of a child to splice two parents instead of copying one
+    #[arg(long, value_parser = probability)]
+    #[arg(default_value_t = 0.9)]
+    pub crossover_rate: f64,
+
+    /// Probability of each color of a child to be changed
+    #[arg(long, value_parser = probability)]
+    #[arg(default_value_t = 0.02)]
+    pub mutation_rate: f64,
+
+    /// Best individuals copied unchanged into the next generation
+    #[arg(long)]
+    #[arg(default_value_t = 2)]
+    pub elite: usize,
+
+    /// File of colorings, one per line, starting the first generation
+    #[arg(long)]
+    pub seeds: Option<PathBuf>,
+
+    /// Seed of the first individual, the following individuals use the next seeds
+    #[arg(long)]
+    #[arg(default_value_t = 0)]
+    pub seed: u64,
+
+    #[arg(short, long)]
+    pub workers: Option<usize>,
+
+    /// Append the best individuals there, one per line as comma-separated colors
+    #[arg(short, long)]
+    pub output_file: Option<PathBuf>,
+
+    /// Number of distinct best individuals written
+    #[arg(long)]
+    #[arg(default_value_t = 10)]
+    pub keep: usize,
+}
+
+#[derive(Args)]
+pub struct CnfArgs {
+    #[arg(short, long, value_enum)]
+    pub problem: Problem,
+
+    #[arg(short, long)]
+    pub colors: usize,
+
+    /// Numbers colored, the upper bound by default
+    #[arg(short = 'n', long)]
+    pub size: Option<usize>,
+
+    /// Add the clauses giving at most one color to each number
+    #[arg(long)]
+    pub at_most_one: bool,
+
+    /// Comma separated colors of the first numbers
+    #[arg(long, value_delimiter = ',')]
+    pub prefix: Option<Vec<usize>>,
+
+    /// File containing the colors of the first numbers
+    #[arg(long, conflicts_with = "prefix")]
+    pub prefix_file: Option<PathBuf>,
+
+    /// The standard output by default
+    #[arg(short, long)]
+    pub output_file: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub symmetry: SymmetryBreakingArgs,
+}
+
+#[derive(Args)]
+pub struct ImportArgs {
+    #[arg(short, long, value_enum)]
+    pub problem: Problem,
+
+    #[arg(short, long)]
+    pub colors: usize,
+
+    /// Output of the solver or solution file, the standard input for -
+    pub model: PathBuf,
+
+    /// Numbers colored, the largest number of the model by default
+    #[arg(short = 'n', long)]
+    pub size: Option<usize>,
+
+    /// Append the coloring there, as comma-separated colors usable as seeds or prefix
+    #[arg(short, long)]
+    pub output_file: Option<PathBuf>,
+
+    /// Write the prefixes of the coloring as a dataset in the trajectory mode
+    #[arg(long)]
+    pub dataset: Option<PathBuf>,
+}
+
+#[cfg(feature = "sat")]
+#[derive(Args)]
+pub struct SolveArgs {
+    #[arg(short, long, value_enum)]
+    pub problem: Problem,
+
+    #[arg(short, long)]
+    pub colors: usize,
+
+    /// Numbers colored, the upper bound by default
+    #[arg(short = 'n', long)]
+    pub size: Option<usize>,
+
+    /// Comma separated colors of the first numbers
+    #[arg(long, value_delimiter = ',')]
+    pub prefix: Option<Vec<usize>>,
+
+    /// File containing the colors of the first numbers
+    #[arg(long, conflicts_with = "prefix")]
+    pub prefix_file: Option<PathBuf>,
+
+    /// Append the coloring found there, as comma-separated colors
+    #[arg(short, long)]
+    pub output_file: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub symmetry: SymmetryBreakingArgs,
+}
+
+#[derive(Args)]
+pub struct SymmetryArgs {
+    #[arg(short, long, value_enum)]
+    pub problem: Problem,
+
+    #[arg(short, long)]
+    pub colors: usize,
+
+    /// Numbers colored, by default the largest size the DFS without symmetry breaking exhausts
+    #[arg(short = 'n', long)]
+    pub size: Option<usize>,
+
+    /// Stop each DFS after visiting this many colorings
+    #[arg(long)]
+    #[arg(default_value_t = 10_000_000)]
+    pub max_nodes: usize,
+
+    /// Also time the built-in SAT solver on each CNF
+    #[cfg(feature = "sat")]
+    #[arg(long)]
+    pub sat: bool,
+}
+
+#[derive(Args)]
+pub struct SymmetryBreakingArgs {
+    /// Use the colors in order of first appearance
+    #[arg(long)]
+    pub first_appearance: bool,
+
+    /// Fix the colors of the first numbers up to a permutation, like 1 and 2 for Schur
+    #[arg(long)]
+    pub fixed_start: bool,
+}
+
+#[derive(Args)]
+pub struct PolicyArgs {
+    /// Policy playing the games
+    #[arg(long, value_enum)]
+    #[arg(default_value_t = PolicyKind::Uniform)]
+    pub policy: PolicyKind,
+
+    /// Temperature of the softmax over the heuristic scores, 0 is greedy
+    #[arg(long, value_parser = temperature)]
+    #[arg(default_value_t = 1.0)]
+    pub temperature: f64,
+
+    /// Number of moves explored by the lookahead policy
+    #[arg(long)]
+    #[arg(default_value_t = 3)]
+    pub lookahead_depth: usize,
+
+    /// Shell command of the external policy, see `ramsey_theory::policies::external`
+    #[cfg(feature = "external")]
+    #[arg(long, required_if_eq("policy", "external"))]
+    pub policy_command: Option<String>,
+
+    /// ONNX model of the onnx policy, see `ramsey_theory::policies::onnx`
+    #[cfg(feature = "onnx")]
+    #[arg(long, required_if_eq("policy", "onnx"))]
+    pub policy_model: Option<PathBuf>,
+}
+
+impl PolicyArgs {
+    /// Arguments of a policy without a subprocess or a model.
+    pub fn new(policy: PolicyKind, temperature: f64, lookahead_depth: usize) -> Self {
+        Self {
+            policy,
+            temperature,
+            lookahead_depth,
+            #[cfg(feature = "external")]
+            policy_command: None,
+            #[cfg(feature = "onnx")]
+            policy_model: None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum Mode {
+    /// One random truncation of each random game
+    Partial,
+    /// Every prefix of each random game
+    Trajectory,
+    /// Each random game without truncation
+    Complete,
+}
+
+impl Mode {
+    /// Whether the duplicated colorings are dropped without `--dedup` or `--no-dedup`.
+    pub fn dedup_by_default(self) -> bool {
+        self == Mode::Partial
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum PolicyKind {
+    Uniform,
+    FirstLegal,
+    /// Prefer the colors with the most remaining possible numbers
+    RemainingPossible,
+    /// Prefer the colors with the fewest remaining possible numbers
+    MostConstrained,
+    /// Prefer the colors removing the fewest possibilities
+    LeastConstraining,
+    /// Prefer the colors leading to the longest continuation
+    Lookahead,
+    /// Ask a subprocess for the probabilities of the colors
+    #[cfg(feature = "external")]
+    External,
+    /// Softmax over the logits of an ONNX model
+    #[cfg(feature = "onnx")]
+    Onnx,
+}
+
+impl PolicyKind {
+    /// Whether the policy runs without a subprocess or a model.
+    pub fn is_builtin(self) -> bool {
+        matches!(
+            self,
+            PolicyKind::Uniform
+                | PolicyKind::FirstLegal
+                | PolicyKind::RemainingPossible
+                | PolicyKind::MostConstrained
+                | PolicyKind::LeastConstraining
+                | PolicyKind::Lookahead
+        )
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum EvaluatorKind {
+    /// Colors still possible for the uncolored numbers, as a final size
+    Possible,
+    /// Mean final size of games played by the policy
+    Rollout,
+    /// Value output of an ONNX model
+    #[cfg(feature = "onnx")]
+    Onnx,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum Fitness {
+    /// Length of the coloring, the children are repaired into legal games played to the end
+    Prefix,
+    /// Opposite of the number of monochromatic solutions of a coloring of a fixed size
+    Violations,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum SizeDistribution {
+    Uniform,
+    Custom,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -0,0 +1,82 @@
+use crate::{
+    cli::{Cli, CnfArgs},
+    prefix::checked_prefix,
+};
+use clap::{CommandFactory, error::ErrorKind};
+use ramsey_theory::{
+    SequenceProblem,
+    cnf::{Cnf, CnfConfig},
+    problems::ForbiddenSolutions,
+    symmetry::SymmetryBreaking,
+};
+use std::{
+    any,
+    fs::File,
+    io::{self, BufWriter},
+};
+
+pub fn cnf<P>(args: CnfArgs)
+where
+    P: SequenceProblem + ForbiddenSolutions,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let size = args.size.unwrap_or(P::BOUND - 1);
+
+    let symmetry = SymmetryBreaking {
+        first_appearance: args.symmetry.first_appearance,
+        fixed_start: args.symmetry.fixed_start,
+    };
+    let prefix = checked_prefix::<P>(
+        args.prefix.as_deref(),
+        args.prefix_file.as_deref(),
+        symmetry,
+        size,
+    );
+
+    let config = CnfConfig {
+        at_most_one: args.at_most_one,
+        prefix,
+        symmetry,
+    };
+    let cnf = Cnf::encode::<P>(size, &config);
+
+    let mut comments = vec![
+        format!("{} on 1..={size}", any::type_name::<P>()),
+        format!(
+            "variable (x - 1) * {} + c + 1: x has the color c",
+            P::N_COLORS
+        ),
+    ];
+    if !config.prefix.is_empty() {
+        let prefix: Vec<_> = config.prefix.iter().map(|c| c.to_string()).collect();
+        comments.push(format!("prefix {}", prefix.join(",")));
+    }
+    if symmetry.first_appearance {
+        comments.push("colors in order of first appearance".to_owned());
+    }
+    if symmetry.fixed_start {
+        let start: Vec<_> = P::canonical_start().iter().map(|c| c.to_string()).collect();
+        comments.push(format!("fixed start {}", start.join(",")));
+    }
+
+    let written = match &args.output_file {
+        Some(filename) => File::create(filename)
+            .and_then(|file| cnf.write_dimacs(BufWriter::new(file), &comments))
+            .map_err(|err| format!("cannot write {}: {err}", filename.display())),
+        None => cnf
+            .write_dimacs(io::stdout().lock(), &comments)
+            .map_err(|err| format!("cannot write the CNF: {err}")),
+    };
+    if let Err(message) = written {
+        Cli::command().error(ErrorKind::Io, message).exit();
+    }
+
+    if args.output_file.is_some() {
+        println!(
+            "{} variables, {} clauses",
+            cnf.n_variables,
+            cnf.clauses.len()
+        );
+    }
+}
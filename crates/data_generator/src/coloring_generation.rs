@@ -1,6 +1,10 @@
-use ramsey_theory::{Coloring, CompressedColoring, SequenceProblem, assert_const_generics::*};
-use rand::{Rng, thread_rng};
+use crate::{cli::Mode, mutation::mutated_start, prefix::replay, size_distribution::SizeQuotas};
+use ramsey_theory::{
+    Coloring, CompressedColoring, Policy, SequenceProblem, assert_const_generics::*,
+};
+use rand::{Rng, seq::SliceRandom, thread_rng};
 use std::{
+    array,
     collections::HashSet,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -9,28 +13,267 @@ use std::{
     thread,
 };
 
+pub struct GenerationConfig {
+    pub mode: Mode,
+    pub canonical: bool,
+    pub dedup: bool,
+    pub quotas: Option<SizeQuotas>,
+    pub dead_end_depth: usize,
+    pub dead_end_oversampling: f64,
+    pub prefix: Vec<usize>,
+    pub seeds: Option<Vec<Vec<usize>>>,
+    pub n_mutations: usize,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub struct Sample<const N_COLORS: usize> {
+    pub coloring: CompressedColoring<N_COLORS>,
+    pub game_id: usize,
+    pub final_size: usize,
+}
+
+struct Game<const N_COLORS: usize> {
+    prefixes: Vec<CompressedColoring<N_COLORS>>,
+    final_size: usize,
+}
+
+struct Buckets<'a> {
+    quotas: &'a SizeQuotas,
+    counts: Vec<usize>,
+    stale_games: Vec<usize>,
+}
+
+impl<'a> Buckets<'a> {
+    fn new(quotas: &'a SizeQuotas) -> Self {
+        Self {
+            quotas,
+            counts: vec![0; quotas.quotas.len()],
+            stale_games: vec![0; quotas.quotas.len()],
+        }
+    }
+
+    fn is_open(&self, bucket: usize) -> bool {
+        self.counts[bucket] < self.quotas.quotas[bucket]
+            && self.stale_games[bucket] < self.quotas.patience
+    }
+
+    fn is_full(&self) -> bool {
+        (0..self.counts.len()).all(|bucket| !self.is_open(bucket))
+    }
+
+    fn open_bucket(&self, size: usize) -> Option<usize> {
+        self.quotas
+            .bucket(size)
+            .filter(|&bucket| self.is_open(bucket))
+    }
+
+    fn insert(&mut self, bucket: usize) {
+        self.counts[bucket] += 1;
+        self.stale_games[bucket] = 0;
+    }
+
+    fn end_game(&mut self) {
+        self.stale_games.iter_mut().for_each(|stale| *stale += 1);
+    }
+
+    fn report_saturation(&self) {
+        for (bucket, (&count, &quota)) in self.counts.iter().zip(&self.quotas.quotas).enumerate() {
+            if count < quota {
+                eprintln!(
+                    "Size {} saturated with {count}/{quota} samples",
+                    self.quotas.min_size + bucket
+                );
+            }
+        }
+    }
+}
+
+struct Collector<'a, const N_COLORS: usize> {
+    n_samples: usize,
+    n_games: usize,
+    samples: Vec<Sample<N_COLORS>>,
+    seen: Option<HashSet<CompressedColoring<N_COLORS>>>,
+    buckets: Option<Buckets<'a>>,
+}
+
+impl<'a, const N_COLORS: usize> Collector<'a, N_COLORS> {
+    fn new(n_samples: usize, config: &'a GenerationConfig) -> Self {
+        Self {
+            n_samples,
+            n_games: 0,
+            samples: Vec::with_capacity(n_samples),
+            seen: config.dedup.then(|| HashSet::with_capacity(n_samples)),
+            buckets: config.quotas.as_ref().map(Buckets::new),
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        match &self.buckets {
+            Some(buckets) => buckets.is_full(),
+            None => self.samples.len() >= self.n_samples,
+        }
+    }
+
+    fn insert_game(&mut self, game: Game<N_COLORS>) {
+        let game_id = self.n_games;
+        self.n_games += 1;
+
+        for coloring in game.prefixes {
+            let bucket = match &self.buckets {
+                Some(buckets) => match buckets.open_bucket(coloring.size()) {
+                    Some(bucket) => Some(bucket),
+                    None => continue,
+                },
+                None => None,
+            };
+
+            let is_new = self
+                .seen
+                .as_mut()
+                .is_none_or(|seen| seen.insert(coloring.clone()));
+
+            if is_new {
+                self.samples.push(Sample {
+                    coloring,
+                    game_id,
+                    final_size: game.final_size,
+                });
+
+                if let (Some(buckets), Some(bucket)) = (&mut self.buckets, bucket) {
+                    buckets.insert(bucket);
+                }
+            }
+        }
+
+        if let Some(buckets) = &mut self.buckets {
+            buckets.end_game();
+        }
+    }
+
+    fn into_samples(self) -> Vec<Sample<N_COLORS>> {
+        if let Some(buckets) = &self.buckets {
+            buckets.report_saturation();
+        }
+
+        self.samples
+    }
+}
+
 // Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
 #[allow(clippy::eq_op)]
-fn coloring_generation<P, R>(
-    n_samples: usize,
+fn partial_size<P, R>(
+    start_size: usize,
+    final_size: usize,
+    config: &GenerationConfig,
     rng: &mut R,
-) -> HashSet<CompressedColoring<{ P::N_COLORS }>>
+) -> usize
 where
     R: Rng + ?Sized,
     P: SequenceProblem,
+{
+    let is_dead_end = final_size < P::BOUND - 1;
+
+    if is_dead_end && rng.gen_bool(config.dead_end_oversampling) {
+        let min_size = final_size.saturating_sub(config.dead_end_depth);
+        rng.gen_range(min_size.max(start_size)..=final_size)
+    } else if start_size < final_size {
+        rng.gen_range(start_size..final_size)
+    } else {
+        final_size
+    }
+}
+
+// Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+#[allow(clippy::eq_op)]
+fn game_generation<P, R>(
+    config: &GenerationConfig,
+    policy: &(dyn Policy<P> + Sync),
+    rng: &mut R,
+) -> Game<{ P::N_COLORS }>
+where
+    R: Rng,
+    P: SequenceProblem,
+    Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let start = match &config.seeds {
+        Some(seeds) => mutated_start::<P, _>(seeds, config.n_mutations, rng),
+        // The prefix has been checked before the generation started.
+        None => replay::<P>(&config.prefix).unwrap(),
+    };
+    let start_size = start.size();
+
+    let mut coloring = Coloring::<{ P::N_COLORS }>::random_from(start, policy, rng);
+    let final_size = coloring.len();
+
+    // The first-appearance order of a prefix is a prefix of the full order.
+    if config.canonical {
+        coloring.order_colors();
+    }
+
+    // With quotas, every prefix in the size range is a candidate for its bucket.
+    let sizes = match (&config.quotas, config.mode) {
+        (_, Mode::Complete) => final_size..=final_size,
+        (Some(quotas), _) => quotas.min_size.max(start_size)..=quotas.max_size().min(final_size),
+        (None, Mode::Trajectory) => start_size..=final_size,
+        (None, Mode::Partial) => {
+            let size = partial_size::<P, _>(start_size, final_size, config, rng);
+            size..=size
+        }
+    };
+
+    let prefixes = sizes
+        .map(|size| CompressedColoring::from(coloring.prefix(size)))
+        .collect();
+
+    Game {
+        prefixes,
+        final_size,
+    }
+}
+
+// Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+#[allow(clippy::eq_op)]
+fn coloring_generation<P, R>(
+    n_games: usize,
+    config: &GenerationConfig,
+    policy: &(dyn Policy<P> + Sync),
+    rng: &mut R,
+) -> Vec<Game<{ P::N_COLORS }>>
+where
+    R: Rng,
+    P: SequenceProblem,
+    Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    (0..n_games)
+        .map(|_| game_generation::<P, _>(config, policy, rng))
+        .collect()
+}
+
+// Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+#[allow(clippy::eq_op)]
+fn single_thread_coloring_generator<P>(
+    n_samples: usize,
+    config: &GenerationConfig,
+    policy: &(dyn Policy<P> + Sync),
+) -> Vec<Sample<{ P::N_COLORS }>>
+where
+    P: SequenceProblem,
     Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
     [(); P::BOUND]:,
     [(); P::N_COLORS]:,
 {
-    let mut colorings = HashSet::with_capacity(n_samples);
+    let mut collector = Collector::new(n_samples, config);
+    let mut rng = thread_rng();
 
-    while colorings.len() < n_samples {
-        let coloring = Coloring::<{ P::N_COLORS }>::random_partial::<P, _>(rng);
-        let compressed = CompressedColoring::from(coloring);
-        colorings.insert(compressed);
+    while !collector.is_full() {
+        collector.insert_game(game_generation::<P, _>(config, policy, &mut rng));
     }
 
-    colorings
+    collector.into_samples()
 }
 
 // Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
@@ -39,14 +282,16 @@ fn mpsc_coloring_generator<P>(
     n_samples: usize,
     n_workers: usize,
     chunk_size: usize,
-) -> HashSet<CompressedColoring<{ P::N_COLORS }>>
+    config: &GenerationConfig,
+    policy: &(dyn Policy<P> + Sync),
+) -> Vec<Sample<{ P::N_COLORS }>>
 where
     P: SequenceProblem,
     Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
     [(); P::BOUND]:,
     [(); P::N_COLORS]:,
 {
-    let mut colorings = HashSet::with_capacity(n_samples + n_workers * chunk_size);
+    let mut collector = Collector::new(n_samples, config);
 
     let keep_running = AtomicBool::new(true);
 
@@ -61,8 +306,8 @@ where
                 let mut rng = thread_rng();
 
                 while keep_running.load(Ordering::Acquire) {
-                    let colorings = coloring_generation::<P, _>(chunk_size, &mut rng);
-                    tx.send(colorings).unwrap();
+                    let games = coloring_generation::<P, _>(chunk_size, config, policy, &mut rng);
+                    tx.send(games).unwrap();
                 }
             });
         }
@@ -73,16 +318,18 @@ where
         let mut signal_not_sent = true;
 
         while let Ok(received) = rx.recv() {
-            colorings.extend(received);
+            received
+                .into_iter()
+                .for_each(|game| collector.insert_game(game));
 
-            if signal_not_sent && colorings.len() >= n_samples {
+            if signal_not_sent && collector.is_full() {
                 keep_running.store(false, Ordering::Release);
                 signal_not_sent = false;
             }
         }
     });
 
-    colorings
+    collector.into_samples()
 }
 
 // Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
@@ -91,18 +338,102 @@ pub fn generate_colorings<P>(
     n_samples: usize,
     n_workers: usize,
     chunk_size: usize,
-) -> Vec<CompressedColoring<{ P::N_COLORS }>>
+    config: &GenerationConfig,
+    policy: &(dyn Policy<P> + Sync),
+) -> Vec<Sample<{ P::N_COLORS }>>
 where
     P: SequenceProblem,
     Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
     [(); P::BOUND]:,
     [(); P::N_COLORS]:,
 {
-    let colorings = if n_workers <= 1 {
-        coloring_generation::<P, _>(n_samples, &mut thread_rng())
+    if n_workers <= 1 {
+        single_thread_coloring_generator::<P>(n_samples, config, policy)
     } else {
-        mpsc_coloring_generator::<P>(n_samples, n_workers, chunk_size)
-    };
+        mpsc_coloring_generator::<P>(n_samples, n_workers, chunk_size, config, policy)
+    }
+}
+
+/// Relabels each game with `n_permutations` random permutations of the colors, every copy of a
+/// game being a game of its own. The samples of a game are consecutive.
+pub fn augment_colorings<const N_COLORS: usize>(
+    samples: Vec<Sample<N_COLORS>>,
+    n_permutations: usize,
+) -> Vec<Sample<N_COLORS>> {
+    let mut rng = thread_rng();
+    let mut augmented = Vec::with_capacity(samples.len() * n_permutations);
+
+    for game in samples.chunk_by(|a, b| a.game_id == b.game_id) {
+        let colorings: Vec<_> = game
+            .iter()
+            .map(|sample| Coloring::from(sample.coloring.clone()))
+            .collect();
+
+        for copy in 0..n_permutations {
+            let mut permutation = array::from_fn(|color| color);
+            permutation.shuffle(&mut rng);
+
+            for (sample, coloring) in game.iter().zip(&colorings) {
+                let mut permuted = coloring.clone();
+                permuted.permute_colors(&permutation);
+
+                augmented.push(Sample {
+                    coloring: CompressedColoring::from(permuted),
+                    game_id: sample.game_id * n_permutations + copy,
+                    final_size: sample.final_size,
+                });
+            }
+        }
+    }
+
+    augmented
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ramsey_theory::{policies::UniformRandom, problems::Schur};
 
-    colorings.into_iter().collect()
+    fn config(mode: Mode, quotas: Option<SizeQuotas>) -> GenerationConfig {
+        GenerationConfig {
+            mode,
+            canonical: false,
+            dedup: false,
+            quotas,
+            dead_end_depth: 0,
+            dead_end_oversampling: 0.0,
+            prefix: Vec::new(),
+            seeds: None,
+            n_mutations: 0,
+        }
+    }
+
+    #[test]
+    fn quotas() {
+        let quotas = SizeQuotas {
+            min_size: 2,
+            quotas: vec![3, 0, 5],
+            patience: 1000,
+        };
+        let config = config(Mode::Partial, Some(quotas));
+        let samples = generate_colorings::<Schur<3>>(100, 1, 10, &config, &UniformRandom);
+
+        let mut counts = [0; 3];
+        for sample in &samples {
+            counts[sample.coloring.size() - 2] += 1;
+        }
+        assert_eq!(counts, [3, 0, 5]);
+    }
+
+    #[test]
+    fn saturated_before_any_sample() {
+        let quotas = SizeQuotas {
+            min_size: 0,
+            quotas: vec![10; 14],
+            patience: 0,
+        };
+        let config = config(Mode::Partial, Some(quotas));
+
+        assert!(generate_colorings::<Schur<3>>(100, 1, 10, &config, &UniformRandom).is_empty());
+    }
 }
@@ -0,0 +1,112 @@
+use crate::{
+    cli::{Cli, EvaluateArgs},
+    policy::build_policy,
+    prefix::format_colors,
+};
+use clap::{CommandFactory, error::ErrorKind};
+use ramsey_theory::{Coloring, Policy, SequenceProblem, assert_const_generics::*};
+use rand::{SeedableRng, rngs::StdRng};
+use std::{
+    cmp::Reverse,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+// Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+#[allow(clippy::eq_op)]
+fn play_games<P>(
+    n_games: usize,
+    seed: u64,
+    n_workers: usize,
+    policy: &(dyn Policy<P> + Sync),
+) -> Vec<Coloring<{ P::N_COLORS }>>
+where
+    P: SequenceProblem,
+    Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let next_game = AtomicUsize::new(0);
+    let mut colorings = vec![None; n_games];
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..n_workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut played = Vec::new();
+
+                    loop {
+                        let game = next_game.fetch_add(1, Ordering::Relaxed);
+                        if game >= n_games {
+                            break played;
+                        }
+
+                        // Each game has its own seed so the results do not depend on the scheduling.
+                        let mut rng = StdRng::seed_from_u64(seed.wrapping_add(game as u64));
+                        played.push((game, Coloring::random::<P, _, _>(policy, &mut rng)));
+                    }
+                })
+            })
+            .collect();
+
+        for worker in workers {
+            for (game, coloring) in worker.join().unwrap() {
+                colorings[game] = Some(coloring);
+            }
+        }
+    });
+
+    colorings.into_iter().map(Option::unwrap).collect()
+}
+
+fn quantile(sorted_sizes: &[usize], q: f64) -> usize {
+    sorted_sizes[((sorted_sizes.len() - 1) as f64 * q).round() as usize]
+}
+
+// Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+#[allow(clippy::eq_op)]
+pub fn evaluate<P>(args: EvaluateArgs)
+where
+    P: SequenceProblem,
+    Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    if args.games == 0 {
+        Cli::command()
+            .error(ErrorKind::ValueValidation, "at least one game is needed")
+            .exit();
+    }
+
+    let n_workers = args.workers.unwrap_or_else(num_cpus::get_physical).max(1);
+    let policy = build_policy::<P>(&args.policy);
+
+    let colorings = play_games::<P>(args.games, args.seed, n_workers, policy.as_ref());
+
+    let max_size = P::BOUND - 1;
+    let mut sizes: Vec<_> = colorings.iter().map(|coloring| coloring.len()).collect();
+    sizes.sort_unstable();
+    let mean = sizes.iter().sum::<usize>() as f64 / sizes.len() as f64;
+
+    println!("{} games, upper bound {max_size}", args.games);
+    println!("mean   {mean:>7.2} ({:.3})", mean / max_size as f64);
+    for (name, q) in [
+        ("min", 0.0),
+        ("q10", 0.1),
+        ("q25", 0.25),
+        ("median", 0.5),
+        ("q75", 0.75),
+        ("q90", 0.9),
+        ("max", 1.0),
+    ] {
+        let size = quantile(&sizes, q);
+        println!("{name:<6} {size:>7} ({:.3})", size as f64 / max_size as f64);
+    }
+
+    // The first of the longest colorings, to stay deterministic.
+    let best = colorings
+        .iter()
+        .min_by_key(|coloring| Reverse(coloring.len()))
+        .unwrap();
+    println!("best coloring: {}", format_colors(best));
+}
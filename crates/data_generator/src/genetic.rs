@@ -0,0 +1,238 @@
+use crate::{
+    cli::{Cli, Fitness, GeneticArgs},
+    mutation::repair,
+    prefix::{format_colors, read_colorings, write_colorings},
+};
+use clap::{CommandFactory, error::ErrorKind};
+use ramsey_theory::{
+    Coloring, SequenceProblem, assert_const_generics::*, problems::ForbiddenSolutions,
+};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use std::{
+    cmp::Reverse,
+    collections::HashSet,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+#[derive(Clone)]
+struct Individual {
+    colors: Vec<usize>,
+    fitness: i64,
+}
+
+// Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+#[allow(clippy::eq_op)]
+fn evaluated<P, R>(mut colors: Vec<usize>, args: &GeneticArgs, rng: &mut R) -> Individual
+where
+    R: Rng + ?Sized,
+    P: SequenceProblem + ForbiddenSolutions,
+    Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    match args.fitness {
+        Fitness::Prefix => {
+            let mut coloring = repair::<P, _>(colors, rng);
+            while let Some(color) = coloring.random_move(rng) {
+                coloring.play(color).expect("Illegal move.");
+            }
+
+            let colors = Coloring::<{ P::N_COLORS }>::from(coloring).to_vec();
+            Individual {
+                fitness: colors.len() as i64,
+                colors,
+            }
+        }
+        Fitness::Violations => {
+            // Required by clap with the violations fitness.
+            let size = args.size.unwrap();
+            colors.truncate(size);
+            while colors.len() < size {
+                colors.push(rng.gen_range(0..P::N_COLORS));
+            }
+
+            Individual {
+                fitness: -(P::monochromatic_count(&colors) as i64),
+                colors,
+            }
+        }
+    }
+}
+
+fn tournament<'a, R>(population: &'a [Individual], size: usize, rng: &mut R) -> &'a Individual
+where
+    R: Rng + ?Sized,
+{
+    (0..size)
+        .map(|_| &population[rng.gen_range(0..population.len())])
+        .max_by_key(|individual| individual.fitness)
+        .unwrap()
+}
+
+// Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+#[allow(clippy::eq_op)]
+fn child<P, R>(population: &[Individual], args: &GeneticArgs, rng: &mut R) -> Individual
+where
+    R: Rng + ?Sized,
+    P: SequenceProblem + ForbiddenSolutions,
+    Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let first = &tournament(population, args.tournament, rng).colors;
+    let mut colors = if rng.gen_bool(args.crossover_rate) {
+        let second = &tournament(population, args.tournament, rng).colors;
+        let cut = rng.gen_range(0..=first.len().min(second.len()));
+
+        [&first[..cut], &second[cut..]].concat()
+    } else {
+        first.clone()
+    };
+
+    for color in &mut colors {
+        if rng.gen_bool(args.mutation_rate) {
+            *color = (*color + rng.gen_range(1..P::N_COLORS)) % P::N_COLORS;
+        }
+    }
+
+    evaluated::<P, _>(colors, args, rng)
+}
+
+/// Computes the individuals `0..n_individuals` in parallel, in the order of their indices.
+fn individuals(
+    n_individuals: usize,
+    n_workers: usize,
+    f: impl Fn(usize) -> Individual + Sync,
+) -> Vec<Individual> {
+    let next_individual = AtomicUsize::new(0);
+    let mut individuals = vec![None; n_individuals];
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..n_workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut computed = Vec::new();
+
+                    loop {
+                        let index = next_individual.fetch_add(1, Ordering::Relaxed);
+                        if index >= n_individuals {
+                            break computed;
+                        }
+
+                        computed.push((index, f(index)));
+                    }
+                })
+            })
+            .collect();
+
+        for worker in workers {
+            for (index, individual) in worker.join().unwrap() {
+                individuals[index] = Some(individual);
+            }
+        }
+    });
+
+    individuals.into_iter().map(Option::unwrap).collect()
+}
+
+fn sort(population: &mut [Individual]) {
+    // Stable, so that the ties keep the order of the indices.
+    population.sort_by_key(|individual| Reverse(individual.fitness));
+}
+
+// Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+#[allow(clippy::eq_op)]
+pub fn genetic<P>(args: GeneticArgs)
+where
+    P: SequenceProblem + ForbiddenSolutions,
+    Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let validation = if args.population == 0 {
+        Some("the population cannot be empty")
+    } else if args.tournament == 0 {
+        Some("the tournament needs at least one individual")
+    } else if args.elite > args.population {
+        Some("the elite is larger than the population")
+    } else {
+        None
+    };
+    if let Some(message) = validation {
+        Cli::command()
+            .error(ErrorKind::ValueValidation, message)
+            .exit();
+    }
+
+    let seeds = match &args.seeds {
+        Some(filename) => {
+            let seeds = read_colorings(filename)
+                .unwrap_or_else(|err| Cli::command().error(ErrorKind::InvalidValue, err).exit());
+            if seeds.is_empty() {
+                Cli::command()
+                    .error(ErrorKind::ValueValidation, "the seeds file is empty")
+                    .exit();
+            }
+            if let Some(color) = seeds.iter().flatten().find(|&&color| color >= P::N_COLORS) {
+                let message = format!("the seeds contain the invalid color {color}");
+                Cli::command()
+                    .error(ErrorKind::ValueValidation, message)
+                    .exit();
+            }
+
+            seeds
+        }
+        None => vec![Vec::new()],
+    };
+
+    let n_workers = args.workers.unwrap_or_else(num_cpus::get_physical).max(1);
+    // Each individual has its own seed so the results do not depend on the scheduling.
+    let rng = |generation: usize, index: usize| {
+        let individual = generation * args.population + index;
+        StdRng::seed_from_u64(args.seed.wrapping_add(individual as u64))
+    };
+
+    let mut population = individuals(args.population, n_workers, |index| {
+        let colors = seeds[index % seeds.len()].clone();
+        evaluated::<P, _>(colors, &args, &mut rng(0, index))
+    });
+    sort(&mut population);
+
+    println!("generation   best     mean");
+    for generation in 1..=args.generations {
+        let children = individuals(args.population - args.elite, n_workers, |index| {
+            child::<P, _>(&population, &args, &mut rng(generation, index))
+        });
+
+        population.truncate(args.elite);
+        population.extend(children);
+        sort(&mut population);
+
+        let mean = population
+            .iter()
+            .map(|individual| individual.fitness as f64)
+            .sum::<f64>()
+            / population.len() as f64;
+        println!("{generation:>10} {:>6} {mean:>8.2}", population[0].fitness);
+    }
+
+    let mut seen = HashSet::new();
+    let best: Vec<_> = population
+        .iter()
+        .map(|individual| individual.colors.as_slice())
+        .filter(|&colors| seen.insert(colors))
+        .collect();
+    println!(
+        "best coloring of size {} (upper bound {}, fitness {}): {}",
+        population[0].colors.len(),
+        P::BOUND - 1,
+        population[0].fitness,
+        format_colors(best[0])
+    );
+
+    if let Some(filename) = &args.output_file {
+        write_colorings(filename, &best[..best.len().min(args.keep)])
+            .unwrap_or_else(|err| Cli::command().error(ErrorKind::Io, err).exit());
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{
+    cli::{Cli, ImportArgs, Mode},
+    coloring_generation::Sample,
+    label_generation::generate_dataset,
+    prefix::{format_colors, replay, write_colorings},
+    save_data::save_data,
+};
+use clap::{CommandFactory, error::ErrorKind};
+use ramsey_theory::{
+    Coloring, CompressedColoring, SequenceProblem,
+    assert_const_generics::*,
+    cnf::{decode, parse_model},
+    problems::ForbiddenSolutions,
+};
+use std::{
+    fs,
+    io::{self, Read},
+};
+
+// Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+#[allow(clippy::eq_op)]
+pub fn import<P>(args: ImportArgs)
+where
+    P: SequenceProblem + ForbiddenSolutions,
+    Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let output = if args.model.as_os_str() == "-" {
+        let mut output = String::new();
+        io::stdin()
+            .read_to_string(&mut output)
+            .map(|_| output)
+            .map_err(|err| format!("cannot read the standard input: {err}"))
+    } else {
+        fs::read_to_string(&args.model)
+            .map_err(|err| format!("cannot read {}: {err}", args.model.display()))
+    };
+
+    let colors = output
+        .and_then(|output| parse_model(&output))
+        .and_then(|model| {
+            let max_variable = model.iter().map(|literal| literal.unsigned_abs()).max();
+            let size = args
+                .size
+                .unwrap_or((max_variable.unwrap() as usize).div_ceil(P::N_COLORS));
+
+            decode(&model, size, P::N_COLORS)
+        })
+        .unwrap_or_else(|err| Cli::command().error(ErrorKind::InvalidValue, err).exit());
+
+    let count = P::monochromatic_count(&colors);
+    if count > 0 {
+        let message = format!("the coloring has {count} monochromatic solutions");
+        Cli::command()
+            .error(ErrorKind::ValueValidation, message)
+            .exit();
+    }
+    // Beyond the upper bound, the game cannot replay the coloring.
+    if colors.len() < P::BOUND {
+        if let Err((position, err)) = replay::<P>(&colors) {
+            panic!("The valid coloring is rejected at position {position}: {err}.");
+        }
+    }
+
+    println!(
+        "valid coloring of size {} (upper bound {}): {}",
+        colors.len(),
+        P::BOUND - 1,
+        format_colors(&colors)
+    );
+
+    if let Some(filename) = &args.output_file {
+        write_colorings(filename, &[&colors])
+            .unwrap_or_else(|err| Cli::command().error(ErrorKind::Io, err).exit());
+    }
+
+    if let Some(filename) = args.dataset {
+        if colors.len() >= P::BOUND {
+            let message = "the coloring is too long for the datasets of this problem";
+            Cli::command()
+                .error(ErrorKind::ValueValidation, message)
+                .exit();
+        }
+
+        let coloring: Coloring<{ P::N_COLORS }> = colors.into_iter().collect();
+        let samples = (0..=coloring.len())
+            .map(|size| Sample {
+                coloring: CompressedColoring::from(coloring.prefix(size)),
+                game_id: 0,
+                final_size: coloring.len(),
+            })
+            .collect();
+
+        let dataset = generate_dataset::<P>(samples, Mode::Trajectory, None, false);
+        save_data(filename, dataset);
+    }
+}
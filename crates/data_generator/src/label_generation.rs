@@ -1,5 +1,9 @@
-use ndarray::{Array1, Array2};
-use ramsey_theory::{CompressedColoring, CompressedColors, SequenceColoring, SequenceProblem};
+use crate::{cli::Mode, coloring_generation::Sample, save_data::Dataset};
+use ndarray::{Array1, Array2, Array3, Axis};
+use ramsey_theory::{
+    CompressedColoring, SequenceColoring, SequenceProblem,
+    observation::{observation, shape},
+};
 
 fn label_generation<P>(
     colorings: &[CompressedColoring<{ P::N_COLORS }>],
@@ -58,19 +62,138 @@ where
     }
 }
 
-pub fn generate_labels<P>(
-    mut colorings: Vec<CompressedColoring<{ P::N_COLORS }>>,
-) -> (Array2<CompressedColors>, Array1<u32>, Array2<bool>)
+fn generate_trajectory_labels<const N_COLORS: usize>(
+    samples: &[Sample<N_COLORS>],
+) -> (Array1<u32>, Array1<u32>) {
+    let game_ids = samples.iter().map(|sample| sample.game_id as u32).collect();
+    let steps = samples
+        .iter()
+        .map(|sample| sample.coloring.size() as u32)
+        .collect();
+
+    (game_ids, steps)
+}
+
+fn generate_dead_end_labels<P>(samples: &[Sample<{ P::N_COLORS }>], depth: usize) -> Array1<bool>
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    samples
+        .iter()
+        .map(|sample| {
+            let is_dead_end = sample.final_size < P::BOUND - 1;
+            is_dead_end && sample.final_size - sample.coloring.size() <= depth
+        })
+        .collect()
+}
+
+fn generate_complete_game_labels<P>(
+    samples: &[Sample<{ P::N_COLORS }>],
+) -> (Array2<u32>, Array1<u32>)
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let mut legal_move_counts = Array2::zeros((samples.len(), P::BOUND));
+    let mut final_size_histogram = Array1::zeros(P::BOUND);
+
+    for (sample, mut row) in samples.iter().zip(legal_move_counts.rows_mut()) {
+        let mut coloring = SequenceColoring::<P>::new();
+
+        for (step, color) in sample.coloring.decompress().enumerate() {
+            row[step] = coloring.legal_moves().len() as u32;
+            coloring.play(color).unwrap();
+        }
+        if !coloring.is_full() {
+            row[coloring.size()] = coloring.legal_moves().len() as u32;
+        }
+
+        final_size_histogram[coloring.size()] += 1;
+    }
+
+    (legal_move_counts, final_size_histogram)
+}
+
+fn generate_observations<P>(samples: &[Sample<{ P::N_COLORS }>]) -> Array3<f32>
 where
     P: SequenceProblem,
     [(); P::BOUND]:,
     [(); P::N_COLORS]:,
 {
-    let (sizes, legal_moves) = label_generation(&colorings);
+    let (n_channels, len) = shape::<P>();
+    let mut observations = Array3::zeros((samples.len(), n_channels, len));
+
+    for (sample, mut row) in samples.iter().zip(observations.axis_iter_mut(Axis(0))) {
+        let mut coloring = SequenceColoring::<P>::new();
+        for color in sample.coloring.decompress() {
+            coloring.play(color).unwrap();
+        }
+
+        row.assign(&observation(&coloring));
+    }
+
+    observations
+}
+
+fn generate_labels<P>(samples: Vec<Sample<{ P::N_COLORS }>>) -> Dataset
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let final_sizes = samples
+        .iter()
+        .map(|sample| sample.final_size as u32)
+        .collect();
+    let mut colorings: Vec<_> = samples.into_iter().map(|sample| sample.coloring).collect();
+    let (sizes, legal_moves) = label_generation::<P>(&colorings);
 
     CompressedColoring::<{ P::N_COLORS }>::pad_to_longest(&mut colorings);
     let colorings = nested_to_array2(colorings);
     let sizes = Array1::from(sizes);
 
-    (colorings, sizes, legal_moves)
+    Dataset {
+        colorings,
+        sizes,
+        legal_moves,
+        final_sizes,
+        ..Default::default()
+    }
+}
+
+pub fn generate_dataset<P>(
+    samples: Vec<Sample<{ P::N_COLORS }>>,
+    mode: Mode,
+    dead_end_depth: Option<usize>,
+    observations: bool,
+) -> Dataset
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let trajectory_labels =
+        (mode == Mode::Trajectory).then(|| generate_trajectory_labels(&samples));
+    let complete_game_labels =
+        (mode == Mode::Complete).then(|| generate_complete_game_labels::<P>(&samples));
+    let dead_end_labels =
+        dead_end_depth.map(|depth| generate_dead_end_labels::<P>(&samples, depth));
+    let observations = observations.then(|| generate_observations::<P>(&samples));
+
+    let mut dataset = generate_labels::<P>(samples);
+    if let Some((game_ids, steps)) = trajectory_labels {
+        dataset.game_ids = Some(game_ids);
+        dataset.steps = Some(steps);
+    }
+    dataset.dead_end_within = dead_end_labels;
+    if let Some((legal_move_counts, final_size_histogram)) = complete_game_labels {
+        dataset.legal_move_counts = Some(legal_move_counts);
+        dataset.final_size_histogram = Some(final_size_histogram);
+    }
+    dataset.observations = observations;
+
+    dataset
 }
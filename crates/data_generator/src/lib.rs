@@ -1,11 +1,34 @@
 #![allow(incomplete_features)]
 #![feature(generic_const_exprs)]
 
+mod beam;
 mod cli;
+mod cnf;
 mod coloring_generation;
+mod evaluate;
+mod genetic;
+mod import;
 mod label_generation;
+mod local_search;
+mod mutation;
+mod policy;
+mod prefix;
 mod run;
 mod save_data;
+mod size_distribution;
+#[cfg(feature = "sat")]
+mod solve;
+mod symmetry;
 
-pub use cli::Cli;
-pub use run::{get_run_fn_pointer, run};
+pub use cli::{Cli, Command, Mode, PolicyArgs, PolicyKind, Problem};
+pub use coloring_generation::{GenerationConfig, Sample, generate_colorings};
+pub use label_generation::generate_dataset;
+pub use policy::{BoxedPolicy, build_policy};
+#[cfg(feature = "sat")]
+pub use run::get_solve_fn_pointer;
+pub use run::{
+    get_beam_fn_pointer, get_cnf_fn_pointer, get_evaluate_fn_pointer, get_genetic_fn_pointer,
+    get_import_fn_pointer, get_local_search_fn_pointer, get_run_fn_pointer,
+    get_symmetry_fn_pointer, run,
+};
+pub use save_data::{Dataset, save_data};
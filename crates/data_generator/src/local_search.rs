@@ -0,0 +1,70 @@
+use crate::{
+    cli::{Cli, LocalSearchArgs},
+    prefix::{format_colors, write_colorings},
+};
+use clap::{CommandFactory, error::ErrorKind};
+use ramsey_theory::{
+    SequenceProblem,
+    assert_const_generics::*,
+    problems::ForbiddenSolutions,
+    search::local::{LocalSearch, LocalSearchResult},
+};
+use rand::{SeedableRng, rngs::StdRng};
+
+// Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+#[allow(clippy::eq_op)]
+pub fn local_search<P>(args: LocalSearchArgs)
+where
+    P: SequenceProblem + ForbiddenSolutions,
+    Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let max_size = args.max_size.unwrap_or(P::BOUND - 1);
+    if args.size > max_size {
+        let message = format!(
+            "the size {} is above the maximum size {max_size}",
+            args.size
+        );
+        Cli::command()
+            .error(ErrorKind::ValueValidation, message)
+            .exit();
+    }
+
+    let search = LocalSearch {
+        max_steps: args.steps,
+        temperature: args.temperature,
+        cooling: args.cooling,
+        tabu_tenure: args.tabu_tenure,
+    };
+    let mut rng = StdRng::seed_from_u64(args.seed);
+
+    println!(" size    steps energy");
+    let best = search.increasing::<P>(
+        args.size,
+        max_size,
+        &mut rng,
+        |result: &LocalSearchResult<{ P::N_COLORS }>| {
+            println!(
+                "{:>5} {:>8} {:>6}",
+                result.size, result.steps, result.best_energy
+            );
+        },
+    );
+
+    let Some(best) = best else {
+        println!("no coloring of size {} found", args.size);
+        return;
+    };
+    println!(
+        "best coloring of size {} (upper bound {}): {}",
+        best.len(),
+        P::BOUND - 1,
+        format_colors(&best)
+    );
+
+    if let Some(filename) = &args.output_file {
+        write_colorings(filename, &[&best])
+            .unwrap_or_else(|err| Cli::command().error(ErrorKind::Io, err).exit());
+    }
+}
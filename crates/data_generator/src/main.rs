@@ -1,5 +1,11 @@
 use clap::Parser;
-use data_generator::{Cli, get_run_fn_pointer};
+#[cfg(feature = "sat")]
+use data_generator::get_solve_fn_pointer;
+use data_generator::{
+    Cli, Command, get_beam_fn_pointer, get_cnf_fn_pointer, get_evaluate_fn_pointer,
+    get_genetic_fn_pointer, get_import_fn_pointer, get_local_search_fn_pointer, get_run_fn_pointer,
+    get_symmetry_fn_pointer,
+};
 
 fn main() {
     #[cfg(debug_assertions)]
@@ -13,6 +19,46 @@ fn main() {
     }
 
     let cli = Cli::parse();
-    let run = get_run_fn_pointer(cli.problem, cli.colors);
-    run(cli.output_file, cli.samples, cli.workers, cli.chunk_size);
+
+    match cli.command {
+        Some(Command::Evaluate(args)) => {
+            let evaluate = get_evaluate_fn_pointer(args.problem, args.colors);
+            evaluate(args);
+        }
+        Some(Command::Beam(args)) => {
+            let beam = get_beam_fn_pointer(args.problem, args.colors);
+            beam(args);
+        }
+        Some(Command::LocalSearch(args)) => {
+            let local_search = get_local_search_fn_pointer(args.problem, args.colors);
+            local_search(args);
+        }
+        Some(Command::Genetic(args)) => {
+            let genetic = get_genetic_fn_pointer(args.problem, args.colors);
+            genetic(args);
+        }
+        Some(Command::Cnf(args)) => {
+            let cnf = get_cnf_fn_pointer(args.problem, args.colors);
+            cnf(args);
+        }
+        Some(Command::Import(args)) => {
+            let import = get_import_fn_pointer(args.problem, args.colors);
+            import(args);
+        }
+        #[cfg(feature = "sat")]
+        Some(Command::Solve(args)) => {
+            let solve = get_solve_fn_pointer(args.problem, args.colors);
+            solve(args);
+        }
+        Some(Command::Symmetry(args)) => {
+            let symmetry = get_symmetry_fn_pointer(args.problem, args.colors);
+            symmetry(args);
+        }
+        None => {
+            let args = cli.generate;
+            // Without a subcommand, clap requires the problem and the number of colors.
+            let run = get_run_fn_pointer(args.problem.unwrap(), args.colors.unwrap());
+            run(args);
+        }
+    }
 }
@@ -0,0 +1,51 @@
+use ramsey_theory::{SequenceColoring, SequenceProblem};
+use rand::Rng;
+
+pub fn mutated_start<P, R>(
+    seeds: &[Vec<usize>],
+    n_mutations: usize,
+    rng: &mut R,
+) -> SequenceColoring<P>
+where
+    R: Rng + ?Sized,
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let seed = &seeds[rng.gen_range(0..seeds.len())];
+    let truncation = rng.gen_range(0..=seed.len());
+    let mut colors = seed[..truncation].to_vec();
+
+    if !colors.is_empty() && P::N_COLORS > 1 {
+        for _ in 0..n_mutations {
+            let position = rng.gen_range(0..colors.len());
+            let shift = rng.gen_range(1..P::N_COLORS);
+            colors[position] = (colors[position] + shift) % P::N_COLORS;
+        }
+    }
+
+    repair(colors, rng)
+}
+
+/// Replaces each illegal color by a random legal one, stops at the first dead end.
+pub fn repair<P, R>(colors: impl IntoIterator<Item = usize>, rng: &mut R) -> SequenceColoring<P>
+where
+    R: Rng + ?Sized,
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let mut coloring = SequenceColoring::<P>::new();
+    for color in colors {
+        if coloring.play(color).is_ok() {
+            continue;
+        }
+
+        match coloring.random_move(rng) {
+            Some(color) => coloring.play(color).expect("Illegal move."),
+            None => break,
+        }
+    }
+
+    coloring
+}
@@ -0,0 +1,91 @@
+#[cfg(any(feature = "external", feature = "onnx"))]
+use crate::cli::Cli;
+use crate::cli::{PolicyArgs, PolicyKind};
+#[cfg(any(feature = "external", feature = "onnx"))]
+use clap::{CommandFactory, error::ErrorKind};
+#[cfg(feature = "onnx")]
+use ramsey_theory::policies::onnx::OnnxPolicy;
+use ramsey_theory::{
+    Policy, SequenceProblem,
+    heuristics::{LeastConstraining, Lookahead, MostConstrained, RemainingPossible},
+    policies::{Boltzmann, FirstLegal, UniformRandom},
+};
+#[cfg(feature = "external")]
+use ramsey_theory::{SequenceColoring, policies::external::ExternalPolicy};
+#[cfg(feature = "external")]
+use std::process::Command;
+
+pub type BoxedPolicy<P> = Box<dyn Policy<P> + Sync>;
+
+/// External policy exiting with an error when its process fails.
+#[cfg(feature = "external")]
+struct External(ExternalPolicy);
+
+#[cfg(feature = "external")]
+impl<P> Policy<P> for External
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    fn distribution(&self, coloring: &SequenceColoring<P>) -> Vec<f64> {
+        self.distributions(std::slice::from_ref(coloring))
+            .pop()
+            .unwrap()
+    }
+
+    fn distributions(&self, colorings: &[SequenceColoring<P>]) -> Vec<Vec<f64>> {
+        self.0.try_distributions(colorings).unwrap_or_else(|err| {
+            let message = format!("the external policy failed: {err}");
+            Cli::command().error(ErrorKind::Io, message).exit()
+        })
+    }
+}
+
+pub fn build_policy<P>(args: &PolicyArgs) -> BoxedPolicy<P>
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let temperature = args.temperature;
+
+    match args.policy {
+        PolicyKind::Uniform => Box::new(UniformRandom),
+        PolicyKind::FirstLegal => Box::new(FirstLegal),
+        PolicyKind::RemainingPossible => Box::new(Boltzmann::new(RemainingPossible, temperature)),
+        PolicyKind::MostConstrained => Box::new(Boltzmann::new(MostConstrained, temperature)),
+        PolicyKind::LeastConstraining => Box::new(Boltzmann::new(LeastConstraining, temperature)),
+        PolicyKind::Lookahead => {
+            let heuristic = Lookahead {
+                depth: args.lookahead_depth,
+            };
+            Box::new(Boltzmann::new(heuristic, temperature))
+        }
+        #[cfg(feature = "external")]
+        PolicyKind::External => {
+            // Required by clap with the external policy.
+            let command = args.policy_command.as_deref().unwrap();
+
+            let policy = ExternalPolicy::spawn(Command::new("sh").arg("-c").arg(command))
+                .unwrap_or_else(|err| {
+                    let message = format!("cannot start the external policy: {err}");
+                    Cli::command().error(ErrorKind::Io, message).exit()
+                });
+            Box::new(External(policy))
+        }
+        #[cfg(feature = "onnx")]
+        PolicyKind::Onnx => {
+            // Required by clap with the onnx policy.
+            let model = args.policy_model.as_deref().unwrap();
+
+            let policy = OnnxPolicy::load::<P>(model).unwrap_or_else(|err| {
+                let message = format!("cannot load the ONNX model: {err:#}");
+                Cli::command()
+                    .error(ErrorKind::InvalidValue, message)
+                    .exit()
+            });
+            Box::new(policy)
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use crate::cli::Cli;
+use clap::{CommandFactory, error::ErrorKind};
+use ramsey_theory::{
+    PlayError, SequenceColoring, SequenceProblem, problems::ForbiddenSolutions,
+    symmetry::SymmetryBreaking,
+};
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    num::ParseIntError,
+    path::Path,
+};
+
+pub fn parse_colors(colors: &str) -> Result<Vec<usize>, ParseIntError> {
+    colors
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|color| !color.is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+pub fn read_prefix(filename: &Path) -> Result<Vec<usize>, String> {
+    let content = fs::read_to_string(filename)
+        .map_err(|err| format!("cannot read {}: {err}", filename.display()))?;
+
+    parse_colors(&content).map_err(|err| format!("invalid color in {}: {err}", filename.display()))
+}
+
+pub fn read_colorings(filename: &Path) -> Result<Vec<Vec<usize>>, String> {
+    let content = fs::read_to_string(filename)
+        .map_err(|err| format!("cannot read {}: {err}", filename.display()))?;
+
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(line_number, line)| {
+            parse_colors(line).map_err(|err| {
+                let line_number = line_number + 1;
+                format!(
+                    "invalid color in {}:{line_number}: {err}",
+                    filename.display()
+                )
+            })
+        })
+        .collect()
+}
+
+pub fn format_colors(colors: &[usize]) -> String {
+    colors
+        .iter()
+        .map(|color| color.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Appends the colorings to the file, one per line, so that it can be read back as seeds.
+pub fn write_colorings(filename: &Path, colorings: &[&[usize]]) -> Result<(), String> {
+    let content: String = colorings
+        .iter()
+        .map(|colors| format_colors(colors) + "\n")
+        .collect();
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(filename)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .map_err(|err| format!("cannot write {}: {err}", filename.display()))
+}
+
+pub fn replay<P>(colors: &[usize]) -> Result<SequenceColoring<P>, (usize, PlayError)>
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let mut coloring = SequenceColoring::<P>::new();
+
+    for (position, &color) in colors.iter().enumerate() {
+        coloring.play(color).map_err(|err| (position, err))?;
+    }
+
+    Ok(coloring)
+}
+
+/// Exits unless the colors can be played in order, `name` naming them in the error.
+pub fn check_replay<P>(colors: &[usize], name: &str)
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    if let Err((position, err)) = replay::<P>(colors) {
+        let message = format!("{name} is rejected at position {position}: {err}");
+        Cli::command()
+            .error(ErrorKind::ValueValidation, message)
+            .exit();
+    }
+}
+
+/// The prefix of `--prefix` or `--prefix-file`, exiting unless it is legal.
+pub fn legal_prefix<P>(prefix: Option<&[usize]>, prefix_file: Option<&Path>) -> Vec<usize>
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let prefix = match prefix_file {
+        Some(filename) => read_prefix(filename)
+            .unwrap_or_else(|err| Cli::command().error(ErrorKind::InvalidValue, err).exit()),
+        None => prefix.unwrap_or_default().to_vec(),
+    };
+    check_replay::<P>(&prefix, "the prefix");
+
+    prefix
+}
+
+/// Like `legal_prefix`, also exiting unless the prefix is allowed by the symmetry breaking and
+/// at most `size` long.
+pub fn checked_prefix<P>(
+    prefix: Option<&[usize]>,
+    prefix_file: Option<&Path>,
+    symmetry: SymmetryBreaking,
+    size: usize,
+) -> Vec<usize>
+where
+    P: SequenceProblem + ForbiddenSolutions,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let prefix = legal_prefix::<P>(prefix, prefix_file);
+
+    if !symmetry.allows_all::<P>(&prefix) {
+        let message = "the symmetry breaking rules out the prefix, relabel its colors in order \
+                       of first appearance";
+        Cli::command()
+            .error(ErrorKind::ValueValidation, message)
+            .exit();
+    }
+    if prefix.len() > size {
+        let message = format!("the prefix is longer than the size {size}");
+        Cli::command()
+            .error(ErrorKind::ValueValidation, message)
+            .exit();
+    }
+
+    prefix
+}
@@ -1,55 +1,263 @@
 use crate::{
-    cli::Problem as CliProblem, coloring_generation::generate_colorings,
-    label_generation::generate_labels, save_data::save_data,
+    beam::beam,
+    cli::{
+        BeamArgs, Cli, CnfArgs, EvaluateArgs, GenerateArgs, GeneticArgs, ImportArgs,
+        LocalSearchArgs, Mode, Problem as CliProblem, SymmetryArgs,
+    },
+    cnf::cnf,
+    coloring_generation::{GenerationConfig, augment_colorings, generate_colorings},
+    evaluate::evaluate,
+    genetic::genetic,
+    import::import,
+    label_generation::generate_dataset,
+    local_search::local_search,
+    policy::build_policy,
+    prefix::{check_replay, legal_prefix, read_colorings},
+    save_data::save_data,
+    size_distribution::size_quotas,
+    symmetry::symmetry,
 };
+#[cfg(feature = "sat")]
+use crate::{cli::SolveArgs, solve::solve};
 use chrono::Local;
+use clap::{CommandFactory, ValueEnum, error::ErrorKind};
+use ndarray::Array1;
 use ramsey_theory::{
     SequenceProblem,
     assert_const_generics::*,
     problems::{Schur, WeakSchur},
 };
-use std::path::PathBuf;
 
-pub type RunFn = fn(Option<PathBuf>, usize, Option<usize>, usize);
+pub type RunFn = fn(GenerateArgs);
+pub type EvaluateFn = fn(EvaluateArgs);
+pub type BeamFn = fn(BeamArgs);
+pub type LocalSearchFn = fn(LocalSearchArgs);
+pub type GeneticFn = fn(GeneticArgs);
+pub type CnfFn = fn(CnfArgs);
+pub type ImportFn = fn(ImportArgs);
+#[cfg(feature = "sat")]
+pub type SolveFn = fn(SolveArgs);
+pub type SymmetryFn = fn(SymmetryArgs);
 
-macro_rules! get_run_fn {
-    ($params:ident, $problem:tt, { $($cases:literal)* }) => {
-        {let fn_array = [$(run::<$problem<$cases>>),*];
+macro_rules! get_fn {
+    ($function:ident, $params:ident, $problem:tt, { $($cases:literal)* }) => {
+        {let fn_array = [$($function::<$problem<$cases>>),*];
         let cases = [$($cases),*];
         let index = cases.iter().position(|x| *x == $params).unwrap();
         fn_array[index]}
     };
 }
 
+fn unsupported(problem: CliProblem) -> ! {
+    let name = problem.to_possible_value().unwrap();
+    let message = format!("the {} problem is not supported yet", name.get_name());
+    Cli::command()
+        .error(ErrorKind::InvalidValue, message)
+        .exit()
+}
+
 pub fn get_run_fn_pointer(problem: CliProblem, n_colors: usize) -> RunFn {
     match problem {
-        CliProblem::Schur => get_run_fn!(n_colors, Schur, { 2 3 4 5 }),
-        CliProblem::WeakSchur => get_run_fn!(n_colors, WeakSchur, { 2 3 4 5 }),
-        _ => unimplemented!(),
+        CliProblem::Schur => get_fn!(run, n_colors, Schur, { 2 3 4 5 }),
+        CliProblem::WeakSchur => get_fn!(run, n_colors, WeakSchur, { 2 3 4 5 }),
+        problem => unsupported(problem),
+    }
+}
+
+pub fn get_evaluate_fn_pointer(problem: CliProblem, n_colors: usize) -> EvaluateFn {
+    match problem {
+        CliProblem::Schur => get_fn!(evaluate, n_colors, Schur, { 2 3 4 5 }),
+        CliProblem::WeakSchur => get_fn!(evaluate, n_colors, WeakSchur, { 2 3 4 5 }),
+        problem => unsupported(problem),
+    }
+}
+
+pub fn get_beam_fn_pointer(problem: CliProblem, n_colors: usize) -> BeamFn {
+    match problem {
+        CliProblem::Schur => get_fn!(beam, n_colors, Schur, { 2 3 4 5 }),
+        CliProblem::WeakSchur => get_fn!(beam, n_colors, WeakSchur, { 2 3 4 5 }),
+        problem => unsupported(problem),
+    }
+}
+
+pub fn get_local_search_fn_pointer(problem: CliProblem, n_colors: usize) -> LocalSearchFn {
+    match problem {
+        CliProblem::Schur => get_fn!(local_search, n_colors, Schur, { 2 3 4 5 }),
+        CliProblem::WeakSchur => get_fn!(local_search, n_colors, WeakSchur, { 2 3 4 5 }),
+        problem => unsupported(problem),
+    }
+}
+
+pub fn get_genetic_fn_pointer(problem: CliProblem, n_colors: usize) -> GeneticFn {
+    match problem {
+        CliProblem::Schur => get_fn!(genetic, n_colors, Schur, { 2 3 4 5 }),
+        CliProblem::WeakSchur => get_fn!(genetic, n_colors, WeakSchur, { 2 3 4 5 }),
+        problem => unsupported(problem),
+    }
+}
+
+pub fn get_cnf_fn_pointer(problem: CliProblem, n_colors: usize) -> CnfFn {
+    match problem {
+        CliProblem::Schur => get_fn!(cnf, n_colors, Schur, { 2 3 4 5 }),
+        CliProblem::WeakSchur => get_fn!(cnf, n_colors, WeakSchur, { 2 3 4 5 }),
+        problem => unsupported(problem),
+    }
+}
+
+pub fn get_import_fn_pointer(problem: CliProblem, n_colors: usize) -> ImportFn {
+    match problem {
+        CliProblem::Schur => get_fn!(import, n_colors, Schur, { 2 3 4 5 }),
+        CliProblem::WeakSchur => get_fn!(import, n_colors, WeakSchur, { 2 3 4 5 }),
+        problem => unsupported(problem),
+    }
+}
+
+#[cfg(feature = "sat")]
+pub fn get_solve_fn_pointer(problem: CliProblem, n_colors: usize) -> SolveFn {
+    match problem {
+        CliProblem::Schur => get_fn!(solve, n_colors, Schur, { 2 3 4 5 }),
+        CliProblem::WeakSchur => get_fn!(solve, n_colors, WeakSchur, { 2 3 4 5 }),
+        problem => unsupported(problem),
+    }
+}
+
+pub fn get_symmetry_fn_pointer(problem: CliProblem, n_colors: usize) -> SymmetryFn {
+    match problem {
+        CliProblem::Schur => get_fn!(symmetry, n_colors, Schur, { 2 3 4 5 }),
+        CliProblem::WeakSchur => get_fn!(symmetry, n_colors, WeakSchur, { 2 3 4 5 }),
+        problem => unsupported(problem),
+    }
+}
+
+fn print_histogram(histogram: &Array1<u32>, max_size: usize) {
+    let n_games: u32 = histogram.sum();
+    let mean = histogram
+        .iter()
+        .enumerate()
+        .map(|(size, &count)| (size as u32 * count) as f64)
+        .sum::<f64>()
+        / n_games as f64;
+
+    println!("Final sizes of {n_games} games (upper bound {max_size}, mean {mean:.2}):");
+    for (size, &count) in histogram.iter().enumerate() {
+        if count > 0 {
+            println!("{size:>4}: {count}");
+        }
     }
 }
 
 // Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
 #[allow(clippy::eq_op)]
-pub fn run<P>(
-    output_filename: Option<PathBuf>,
-    n_samples: usize,
-    n_workers: Option<usize>,
-    generation_chunk_size: usize,
-) where
+pub fn run<P>(args: GenerateArgs)
+where
     P: SequenceProblem,
     Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
     [(); P::BOUND]:,
     [(); P::N_COLORS]:,
 {
-    let mut output_filename =
-        output_filename.unwrap_or_else(|| Local::now().format("%Y%m%d-%H%M%S").to_string().into());
+    let mut output_filename = args
+        .output_file
+        .unwrap_or_else(|| Local::now().format("%Y%m%d-%H%M%S").to_string().into());
     output_filename.set_extension("npz");
 
-    let n_workers = n_workers.unwrap_or_else(num_cpus::get_physical);
+    let n_workers = args.workers.unwrap_or_else(num_cpus::get_physical);
+
+    let prefix = legal_prefix::<P>(args.prefix.as_deref(), args.prefix_file.as_deref());
+
+    let seeds = args.seeds.as_deref().map(|filename| {
+        let seeds = read_colorings(filename)
+            .unwrap_or_else(|err| Cli::command().error(ErrorKind::InvalidValue, err).exit());
+
+        for (index, seed) in seeds.iter().enumerate() {
+            check_replay::<P>(seed, &format!("the seed {index}"));
+        }
+        if seeds.is_empty() {
+            Cli::command()
+                .error(ErrorKind::ValueValidation, "the seeds file is empty")
+                .exit();
+        }
+
+        seeds
+    });
 
-    let colorings = generate_colorings::<P>(n_samples, n_workers, generation_chunk_size);
-    let (colorings, sizes, legal_moves) = generate_labels::<P>(colorings);
+    if args.dead_end_oversampling.is_some() && args.mode != Mode::Partial {
+        let message = "the dead end oversampling only applies to the partial mode";
+        Cli::command()
+            .error(ErrorKind::ArgumentConflict, message)
+            .exit();
+    }
+
+    // The buckets of the quotas would drop the prefixes of full sizes from the games.
+    if args.size_distribution.is_some() && args.mode == Mode::Trajectory {
+        let message = "the size distribution does not apply to the trajectory mode";
+        Cli::command()
+            .error(ErrorKind::ArgumentConflict, message)
+            .exit();
+    }
+
+    let min_size = args.min_size.unwrap_or(0);
+    let max_size = args.max_size.unwrap_or(P::BOUND - 1);
+    if max_size > P::BOUND - 1 {
+        let message = format!("the maximum size exceeds the upper bound {}", P::BOUND - 1);
+        Cli::command()
+            .error(ErrorKind::ValueValidation, message)
+            .exit();
+    }
+    if min_size > max_size {
+        Cli::command()
+            .error(
+                ErrorKind::ValueValidation,
+                "the minimum size exceeds the maximum size",
+            )
+            .exit();
+    }
+    let quotas = args.size_distribution.map(|distribution| {
+        size_quotas(
+            distribution,
+            args.size_weights.as_deref(),
+            min_size,
+            max_size,
+            args.samples,
+            args.saturation_patience,
+        )
+        .unwrap_or_else(|err| Cli::command().error(ErrorKind::InvalidValue, err).exit())
+    });
+
+    let config = GenerationConfig {
+        mode: args.mode,
+        canonical: args.canonical,
+        dedup: args.dedup || (!args.no_dedup && args.mode.dedup_by_default()),
+        quotas,
+        dead_end_depth: args.dead_end_depth.unwrap_or(0),
+        dead_end_oversampling: args.dead_end_oversampling.unwrap_or(0.0),
+        prefix,
+        seeds,
+        n_mutations: args.mutations,
+    };
+
+    let policy = build_policy::<P>(&args.policy);
+
+    let mut samples = generate_colorings::<P>(
+        args.samples,
+        n_workers,
+        args.chunk_size,
+        &config,
+        policy.as_ref(),
+    );
+    if let Some(n_permutations) = args.augment {
+        samples = augment_colorings(samples, n_permutations);
+    }
+    if samples.is_empty() {
+        let message = "no sample was collected, every size saturated before reaching its quota";
+        Cli::command()
+            .error(ErrorKind::ValueValidation, message)
+            .exit();
+    }
+
+    let dataset = generate_dataset::<P>(samples, args.mode, args.dead_end_depth, args.observations);
+    if let Some(final_size_histogram) = &dataset.final_size_histogram {
+        print_histogram(final_size_histogram, P::BOUND - 1);
+    }
 
-    save_data(output_filename, colorings, sizes, legal_moves)
+    save_data(output_filename, dataset)
 }
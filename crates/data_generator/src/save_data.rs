@@ -1,19 +1,56 @@
-use ndarray::{Array1, Array2};
+use ndarray::{Array1, Array2, Array3};
 use ndarray_npy::NpzWriter;
 use std::{fs::File, path::PathBuf};
 
-pub fn save_data(
-    filename: PathBuf,
-    colorings: Array2<u32>,
-    sizes: Array1<u32>,
-    legal_moves: Array2<bool>,
-) {
+#[derive(Default)]
+pub struct Dataset {
+    pub colorings: Array2<u32>,
+    pub sizes: Array1<u32>,
+    pub legal_moves: Array2<bool>,
+    pub final_sizes: Array1<u32>,
+    pub game_ids: Option<Array1<u32>>,
+    pub steps: Option<Array1<u32>>,
+    pub dead_end_within: Option<Array1<bool>>,
+    pub legal_move_counts: Option<Array2<u32>>,
+    pub final_size_histogram: Option<Array1<u32>>,
+    pub observations: Option<Array3<f32>>,
+    /// Distributions of the colors chosen by a search, the targets of expert iteration.
+    pub policies: Option<Array2<f32>>,
+}
+
+pub fn save_data(filename: PathBuf, dataset: Dataset) {
     let file = File::create(filename).unwrap();
     let mut npz = NpzWriter::new_compressed(file);
 
-    npz.add_array("colorings", &colorings).unwrap();
-    npz.add_array("sizes", &sizes).unwrap();
-    npz.add_array("legal_moves", &legal_moves).unwrap();
+    npz.add_array("colorings", &dataset.colorings).unwrap();
+    npz.add_array("sizes", &dataset.sizes).unwrap();
+    npz.add_array("legal_moves", &dataset.legal_moves).unwrap();
+    npz.add_array("final_size", &dataset.final_sizes).unwrap();
+
+    if let Some(game_ids) = dataset.game_ids {
+        npz.add_array("game_id", &game_ids).unwrap();
+    }
+    if let Some(steps) = dataset.steps {
+        npz.add_array("step", &steps).unwrap();
+    }
+    if let Some(dead_end_within) = dataset.dead_end_within {
+        npz.add_array("dead_end_within", &dead_end_within).unwrap();
+    }
+    if let Some(legal_move_counts) = dataset.legal_move_counts {
+        npz.add_array("legal_move_counts", &legal_move_counts)
+            .unwrap();
+    }
+    if let Some(final_size_histogram) = dataset.final_size_histogram {
+        npz.add_array("final_size_histogram", &final_size_histogram)
+            .unwrap();
+    }
+
+    if let Some(observations) = dataset.observations {
+        npz.add_array("observations", &observations).unwrap();
+    }
+    if let Some(policies) = dataset.policies {
+        npz.add_array("policy", &policies).unwrap();
+    }
 
     npz.finish().unwrap();
 }
@@ -0,0 +1,117 @@
+use crate::cli::SizeDistribution;
+use std::{fs, path::Path};
+
+pub struct SizeQuotas {
+    pub min_size: usize,
+    pub quotas: Vec<usize>,
+    pub patience: usize,
+}
+
+impl SizeQuotas {
+    pub fn max_size(&self) -> usize {
+        self.min_size + self.quotas.len() - 1
+    }
+
+    pub fn bucket(&self, size: usize) -> Option<usize> {
+        size.checked_sub(self.min_size)
+            .filter(|&bucket| bucket < self.quotas.len())
+    }
+}
+
+fn read_size_weights(filename: &Path) -> Result<Vec<(usize, f64)>, String> {
+    let content = fs::read_to_string(filename)
+        .map_err(|err| format!("cannot read {}: {err}", filename.display()))?;
+
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let mut fields = line.split_whitespace();
+            let size = fields
+                .next()
+                .unwrap()
+                .parse()
+                .map_err(|err| format!("invalid size in {line:?}: {err}"))?;
+            let weight: f64 = fields
+                .next()
+                .ok_or_else(|| format!("missing weight in {line:?}"))?
+                .parse()
+                .map_err(|err| format!("invalid weight in {line:?}: {err}"))?;
+            if !(weight.is_finite() && weight >= 0.0) {
+                return Err(format!("negative or infinite weight in {line:?}"));
+            }
+
+            Ok((size, weight))
+        })
+        .collect()
+}
+
+/// Quotas of the sizes in `min_size..=max_size`, which must not be empty.
+pub fn size_quotas(
+    distribution: SizeDistribution,
+    weights_filename: Option<&Path>,
+    min_size: usize,
+    max_size: usize,
+    n_samples: usize,
+    patience: usize,
+) -> Result<SizeQuotas, String> {
+    let mut weights = vec![0.0; max_size - min_size + 1];
+
+    match distribution {
+        SizeDistribution::Uniform => weights.fill(1.0),
+        SizeDistribution::Custom => {
+            // Required by clap with the custom distribution.
+            let filename = weights_filename.unwrap();
+
+            for (size, weight) in read_size_weights(filename)? {
+                if (min_size..=max_size).contains(&size) {
+                    weights[size - min_size] += weight;
+                }
+            }
+        }
+    }
+
+    let total_weight: f64 = weights.iter().sum();
+    if total_weight <= 0.0 {
+        return Err(format!(
+            "the size weights of {min_size}..={max_size} sum to zero"
+        ));
+    }
+
+    let quotas: Vec<_> = weights
+        .into_iter()
+        .map(|weight| (n_samples as f64 * weight / total_weight).round() as usize)
+        .collect();
+    if quotas.iter().sum::<usize>() == 0 {
+        return Err(format!(
+            "{n_samples} samples round every quota of {min_size}..={max_size} to zero"
+        ));
+    }
+
+    Ok(SizeQuotas {
+        min_size,
+        quotas,
+        patience,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotas() {
+        let quotas = size_quotas(SizeDistribution::Uniform, None, 3, 6, 100, 10).unwrap();
+        assert_eq!(quotas.quotas, [25; 4]);
+        assert_eq!(quotas.max_size(), 6);
+        assert_eq!(quotas.bucket(2), None);
+        assert_eq!(quotas.bucket(4), Some(1));
+        assert_eq!(quotas.bucket(7), None);
+    }
+
+    #[test]
+    fn zero_quotas() {
+        assert!(size_quotas(SizeDistribution::Uniform, None, 0, 13, 1, 10).is_err());
+    }
+}
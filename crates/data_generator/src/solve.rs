@@ -0,0 +1,44 @@
+use crate::{
+    cli::{Cli, SolveArgs},
+    prefix::{checked_prefix, format_colors, write_colorings},
+};
+use clap::{CommandFactory, error::ErrorKind};
+use ramsey_theory::{
+    SequenceProblem, assert_const_generics::*, problems::ForbiddenSolutions, sat::complete,
+    symmetry::SymmetryBreaking,
+};
+
+// Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+#[allow(clippy::eq_op)]
+pub fn solve<P>(args: SolveArgs)
+where
+    P: SequenceProblem + ForbiddenSolutions,
+    Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let size = args.size.unwrap_or(P::BOUND - 1);
+
+    let symmetry = SymmetryBreaking {
+        first_appearance: args.symmetry.first_appearance,
+        fixed_start: args.symmetry.fixed_start,
+    };
+    let prefix = checked_prefix::<P>(
+        args.prefix.as_deref(),
+        args.prefix_file.as_deref(),
+        symmetry,
+        size,
+    );
+
+    let Some(coloring) = complete::<P>(size, &prefix, symmetry) else {
+        println!("UNSAT: no coloring of size {size}");
+        return;
+    };
+
+    println!("SAT: {}", format_colors(&coloring));
+
+    if let Some(filename) = &args.output_file {
+        write_colorings(filename, &[&coloring])
+            .unwrap_or_else(|err| Cli::command().error(ErrorKind::Io, err).exit());
+    }
+}
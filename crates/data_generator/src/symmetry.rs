@@ -0,0 +1,104 @@
+use crate::cli::{Cli, SymmetryArgs};
+use clap::{CommandFactory, error::ErrorKind};
+use ramsey_theory::{
+    SequenceProblem,
+    assert_const_generics::*,
+    cnf::{Cnf, CnfConfig},
+    problems::ForbiddenSolutions,
+    search::dfs::Dfs,
+    symmetry::SymmetryBreaking,
+};
+use std::time::Instant;
+
+// Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+#[allow(clippy::eq_op)]
+pub fn symmetry<P>(args: SymmetryArgs)
+where
+    P: SequenceProblem + ForbiddenSolutions,
+    Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let size = args.size.unwrap_or_else(|| {
+        // The largest size exhausted without symmetry breaking, to compare complete counts.
+        let dfs = Dfs {
+            symmetry: SymmetryBreaking::default(),
+            max_nodes: Some(args.max_nodes),
+        };
+        (1..P::BOUND)
+            .take_while(|&size| !dfs.count::<P>(size, &[]).stopped)
+            .last()
+            .unwrap_or(1)
+    });
+    if size >= P::BOUND {
+        let message = format!("the DFS cannot go beyond the upper bound {}", P::BOUND - 1);
+        Cli::command()
+            .error(ErrorKind::ValueValidation, message)
+            .exit();
+    }
+
+    let layers = [
+        ("none", false, false),
+        ("first-appearance", true, false),
+        ("fixed-start", false, true),
+        ("both", true, true),
+    ];
+
+    println!("size {size} (upper bound {})", P::BOUND - 1);
+    print!("layers                dfs nodes reduction      colorings  time (s)   clauses");
+    #[cfg(feature = "sat")]
+    if args.sat {
+        print!("  sat result  time (s)");
+    }
+    println!();
+
+    let mut unbroken = None;
+    for (name, first_appearance, fixed_start) in layers {
+        let symmetry = SymmetryBreaking {
+            first_appearance,
+            fixed_start,
+        };
+
+        let dfs = Dfs {
+            symmetry,
+            max_nodes: Some(args.max_nodes),
+        };
+        let start = Instant::now();
+        // Exhaustive, so that the pruning does not depend on the order of the moves.
+        let result = dfs.count::<P>(size, &[]);
+        let dfs_time = start.elapsed().as_secs_f64();
+        let unbroken = *unbroken.get_or_insert((result.nodes, result.stopped));
+
+        let config = CnfConfig {
+            at_most_one: false,
+            prefix: Vec::new(),
+            symmetry,
+        };
+        let n_clauses = Cnf::encode::<P>(size, &config).clauses.len();
+
+        let colorings = match result.stopped {
+            true => "stopped".to_owned(),
+            false => result.solutions.to_string(),
+        };
+        // The node counts of a stopped search are only lower bounds.
+        let reduction = match unbroken {
+            (nodes, false) if !result.stopped => {
+                format!("{:.1}", nodes as f64 / result.nodes as f64)
+            }
+            _ => "n/a".to_owned(),
+        };
+        print!(
+            "{name:<16} {:>14} {reduction:>9} {colorings:>14} {dfs_time:>9.3} {n_clauses:>9}",
+            result.nodes,
+        );
+        #[cfg(feature = "sat")]
+        if args.sat {
+            let start = Instant::now();
+            let coloring = ramsey_theory::sat::complete::<P>(size, &[], symmetry);
+            let sat_time = start.elapsed().as_secs_f64();
+            let sat = if coloring.is_some() { "SAT" } else { "UNSAT" };
+            print!("  {sat:>10} {sat_time:>9.3}");
+        }
+        println!();
+    }
+}
@@ -0,0 +1,194 @@
+use crate::PlayError;
+use numpy::{IntoPyArray, PyArray1, PyArray2, PyArray3};
+use pyo3::{exceptions::PyValueError, prelude::*};
+use ramsey_theory::{
+    SequenceColoring, SequenceProblem, VecEnv,
+    observation::observation,
+    problems::{Schur, UpperBound, WeakSchur},
+    vec_env::VecEnvConfig,
+};
+
+type StepArrays<'py> = (
+    Bound<'py, PyArray3<f32>>,
+    Bound<'py, PyArray1<f32>>,
+    Bound<'py, PyArray1<bool>>,
+    Bound<'py, PyArray2<bool>>,
+    Bound<'py, PyArray1<u32>>,
+    Vec<Option<String>>,
+);
+
+macro_rules! problem_classes {
+    ($coloring:ident, $vec_env:ident, $problem:ty) => {
+        #[pyclass(module = "ramsey_dl")]
+        #[derive(Clone)]
+        pub struct $coloring(SequenceColoring<$problem>);
+
+        #[pymethods]
+        impl $coloring {
+            #[classattr]
+            const N_COLORS: usize = <$problem as SequenceProblem>::N_COLORS;
+
+            #[classattr]
+            const MAX_SIZE: usize = <$problem as UpperBound>::BOUND - 1;
+
+            #[new]
+            fn new() -> Self {
+                Self(SequenceColoring::new())
+            }
+
+            fn play(&mut self, color: usize) -> PyResult<()> {
+                self.0
+                    .play(color)
+                    .map_err(|err| PlayError::new_err(err.to_string()))
+            }
+
+            fn legal_moves(&self) -> Vec<usize> {
+                self.0.legal_moves()
+            }
+
+            fn size(&self) -> usize {
+                self.0.size()
+            }
+
+            fn is_full(&self) -> bool {
+                self.0.is_full()
+            }
+
+            fn colors(&self) -> Vec<usize> {
+                self.0.clone().into_iter().collect()
+            }
+
+            fn observation<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray2<f32>> {
+                observation(&self.0).into_pyarray(py)
+            }
+
+            fn clone(&self) -> Self {
+                Self(self.0.clone())
+            }
+
+            fn __copy__(&self) -> Self {
+                Self(self.0.clone())
+            }
+
+            fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> Self {
+                Self(self.0.clone())
+            }
+
+            fn __len__(&self) -> usize {
+                self.0.size()
+            }
+
+            fn __repr__(&self) -> String {
+                format!("{}({:?})", stringify!($coloring), self.colors())
+            }
+        }
+
+        #[pyclass(module = "ramsey_dl")]
+        pub struct $vec_env(VecEnv<$problem>);
+
+        #[pymethods]
+        impl $vec_env {
+            #[new]
+            #[pyo3(signature = (n_envs, *, move_reward = None, illegal_move_reward = None, dead_end_reward = None, full_reward = None, illegal_move_ends_game = None))]
+            fn new(
+                n_envs: usize,
+                move_reward: Option<f32>,
+                illegal_move_reward: Option<f32>,
+                dead_end_reward: Option<f32>,
+                full_reward: Option<f32>,
+                illegal_move_ends_game: Option<bool>,
+            ) -> Self {
+                let default = VecEnvConfig::default();
+                let config = VecEnvConfig {
+                    move_reward: move_reward.unwrap_or(default.move_reward),
+                    illegal_move_reward: illegal_move_reward.unwrap_or(default.illegal_move_reward),
+                    dead_end_reward: dead_end_reward.unwrap_or(default.dead_end_reward),
+                    full_reward: full_reward.unwrap_or(default.full_reward),
+                    illegal_move_ends_game: illegal_move_ends_game
+                        .unwrap_or(default.illegal_move_ends_game),
+                };
+
+                Self(VecEnv::new(n_envs, config))
+            }
+
+            /// Returns the observations and the action masks.
+            fn reset<'py>(
+                &mut self,
+                py: Python<'py>,
+            ) -> (Bound<'py, PyArray3<f32>>, Bound<'py, PyArray2<bool>>) {
+                let (observations, action_masks) = self.0.reset();
+
+                (observations.into_pyarray(py), action_masks.into_pyarray(py))
+            }
+
+            /// Returns the observations, rewards, dones, action masks, sizes and errors.
+            fn step<'py>(
+                &mut self,
+                py: Python<'py>,
+                actions: Vec<usize>,
+            ) -> PyResult<StepArrays<'py>> {
+                if actions.len() != self.0.len() {
+                    return Err(PyValueError::new_err(format!(
+                        "{} actions for {} games",
+                        actions.len(),
+                        self.0.len()
+                    )));
+                }
+
+                let step = self.0.step(&actions);
+                let errors = step
+                    .errors
+                    .iter()
+                    .map(|err| err.map(|err| err.to_string()))
+                    .collect();
+
+                Ok((
+                    step.observations.into_pyarray(py),
+                    step.rewards.into_pyarray(py),
+                    step.dones.into_pyarray(py),
+                    step.action_masks.into_pyarray(py),
+                    step.sizes.into_pyarray(py),
+                    errors,
+                ))
+            }
+
+            fn states(&self) -> Vec<$coloring> {
+                self.0.states().iter().cloned().map($coloring).collect()
+            }
+
+            fn __len__(&self) -> usize {
+                self.0.len()
+            }
+        }
+    };
+}
+
+problem_classes!(Schur2, Schur2VecEnv, Schur<2>);
+problem_classes!(Schur3, Schur3VecEnv, Schur<3>);
+problem_classes!(Schur4, Schur4VecEnv, Schur<4>);
+problem_classes!(Schur5, Schur5VecEnv, Schur<5>);
+problem_classes!(WeakSchur2, WeakSchur2VecEnv, WeakSchur<2>);
+problem_classes!(WeakSchur3, WeakSchur3VecEnv, WeakSchur<3>);
+problem_classes!(WeakSchur4, WeakSchur4VecEnv, WeakSchur<4>);
+problem_classes!(WeakSchur5, WeakSchur5VecEnv, WeakSchur<5>);
+
+pub fn add_classes(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Schur2>()?;
+    module.add_class::<Schur2VecEnv>()?;
+    module.add_class::<Schur3>()?;
+    module.add_class::<Schur3VecEnv>()?;
+    module.add_class::<Schur4>()?;
+    module.add_class::<Schur4VecEnv>()?;
+    module.add_class::<Schur5>()?;
+    module.add_class::<Schur5VecEnv>()?;
+    module.add_class::<WeakSchur2>()?;
+    module.add_class::<WeakSchur2VecEnv>()?;
+    module.add_class::<WeakSchur3>()?;
+    module.add_class::<WeakSchur3VecEnv>()?;
+    module.add_class::<WeakSchur4>()?;
+    module.add_class::<WeakSchur4VecEnv>()?;
+    module.add_class::<WeakSchur5>()?;
+    module.add_class::<WeakSchur5VecEnv>()?;
+
+    Ok(())
+}
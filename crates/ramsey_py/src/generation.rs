@@ -0,0 +1,276 @@
+use clap::ValueEnum;
+use data_generator::{
+    Dataset, GenerationConfig, Mode, PolicyArgs, Problem, build_policy,
+    generate_colorings as generate_samples, generate_dataset,
+};
+use numpy::IntoPyArray;
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyDict};
+use ramsey_theory::{
+    SequenceProblem,
+    assert_const_generics::*,
+    problems::{Schur, WeakSchur},
+};
+
+struct Request {
+    n_samples: usize,
+    n_workers: usize,
+    chunk_size: usize,
+    mode: Mode,
+    canonical: bool,
+    dedup: Option<bool>,
+    dead_end_depth: Option<usize>,
+    observations: bool,
+    policy: PolicyArgs,
+}
+
+impl Request {
+    fn validate(&self) -> Result<(), String> {
+        if self.n_samples == 0 {
+            return Err("at least one sample is needed".to_owned());
+        }
+
+        let temperature = self.policy.temperature;
+        if !(temperature.is_finite() && temperature >= 0.0) {
+            return Err(format!(
+                "the temperature {temperature} is not a finite non-negative number"
+            ));
+        }
+
+        // The others would need a model of their own, which is already in Python.
+        if !self.policy.policy.is_builtin() {
+            let name = self.policy.policy.to_possible_value().unwrap();
+            return Err(format!(
+                "the {} policy is not available from Python",
+                name.get_name()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+type GenerateFn = fn(&Request) -> Dataset;
+
+macro_rules! get_fn {
+    ($params:ident, $problem:tt, { $($cases:literal)* }) => {
+        [$(($cases, generate::<$problem<$cases>> as GenerateFn)),*]
+            .into_iter()
+            .find(|&(n_colors, _)| n_colors == $params)
+            .map(|(_, function)| function)
+    };
+}
+
+fn get_generate_fn(problem: Problem, n_colors: usize) -> Option<GenerateFn> {
+    match problem {
+        Problem::Schur => get_fn!(n_colors, Schur, { 2 3 4 5 }),
+        Problem::WeakSchur => get_fn!(n_colors, WeakSchur, { 2 3 4 5 }),
+        _ => None,
+    }
+}
+
+// Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+#[allow(clippy::eq_op)]
+fn generate<P>(request: &Request) -> Dataset
+where
+    P: SequenceProblem,
+    Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let config = GenerationConfig {
+        mode: request.mode,
+        canonical: request.canonical,
+        // The same default as `data_generator`.
+        dedup: request
+            .dedup
+            .unwrap_or_else(|| request.mode.dedup_by_default()),
+        quotas: None,
+        dead_end_depth: request.dead_end_depth.unwrap_or(0),
+        dead_end_oversampling: 0.0,
+        prefix: Vec::new(),
+        seeds: None,
+        n_mutations: 0,
+    };
+    let policy = build_policy::<P>(&request.policy);
+
+    let samples = generate_samples::<P>(
+        request.n_samples,
+        request.n_workers,
+        request.chunk_size,
+        &config,
+        policy.as_ref(),
+    );
+
+    generate_dataset::<P>(
+        samples,
+        request.mode,
+        request.dead_end_depth,
+        request.observations,
+    )
+}
+
+fn parse<T>(name: &str, value: &str) -> Result<T, String>
+where
+    T: ValueEnum,
+{
+    T::from_str(value, true).map_err(|_| format!("unknown {name} {value:?}"))
+}
+
+/// Generates a dataset in memory, as a dict of the arrays saved by `data_generator`.
+#[pyfunction]
+#[pyo3(signature = (
+    problem,
+    colors,
+    samples,
+    *,
+    workers = None,
+    chunk_size = 500,
+    mode = "partial",
+    canonical = false,
+    dedup = None,
+    dead_end_depth = None,
+    observations = false,
+    policy = "uniform",
+    temperature = 1.0,
+    lookahead_depth = 3,
+))]
+#[allow(clippy::too_many_arguments)]
+pub fn generate_colorings<'py>(
+    py: Python<'py>,
+    problem: &str,
+    colors: usize,
+    samples: usize,
+    workers: Option<usize>,
+    chunk_size: usize,
+    mode: &str,
+    canonical: bool,
+    dedup: Option<bool>,
+    dead_end_depth: Option<usize>,
+    observations: bool,
+    policy: &str,
+    temperature: f64,
+    lookahead_depth: usize,
+) -> PyResult<Bound<'py, PyDict>> {
+    let problem_kind = parse("problem", problem).map_err(PyValueError::new_err)?;
+    let generate = get_generate_fn(problem_kind, colors).ok_or_else(|| {
+        PyValueError::new_err(format!("{problem} with {colors} colors is not supported"))
+    })?;
+
+    // The same default as `data_generator`, so that both generate the same datasets.
+    let n_workers = workers.unwrap_or_else(num_cpus::get_physical);
+
+    let request = Request {
+        n_samples: samples,
+        n_workers,
+        chunk_size,
+        mode: parse("mode", mode).map_err(PyValueError::new_err)?,
+        canonical,
+        dedup,
+        dead_end_depth,
+        observations,
+        policy: PolicyArgs::new(
+            parse("policy", policy).map_err(PyValueError::new_err)?,
+            temperature,
+            lookahead_depth,
+        ),
+    };
+    request.validate().map_err(PyValueError::new_err)?;
+
+    let dataset = py.allow_threads(|| generate(&request));
+
+    let arrays = PyDict::new(py);
+    arrays.set_item("colorings", dataset.colorings.into_pyarray(py))?;
+    arrays.set_item("sizes", dataset.sizes.into_pyarray(py))?;
+    arrays.set_item("legal_moves", dataset.legal_moves.into_pyarray(py))?;
+    arrays.set_item("final_size", dataset.final_sizes.into_pyarray(py))?;
+
+    if let Some(game_ids) = dataset.game_ids {
+        arrays.set_item("game_id", game_ids.into_pyarray(py))?;
+    }
+    if let Some(steps) = dataset.steps {
+        arrays.set_item("step", steps.into_pyarray(py))?;
+    }
+    if let Some(dead_end_within) = dataset.dead_end_within {
+        arrays.set_item("dead_end_within", dead_end_within.into_pyarray(py))?;
+    }
+    if let Some(legal_move_counts) = dataset.legal_move_counts {
+        arrays.set_item("legal_move_counts", legal_move_counts.into_pyarray(py))?;
+    }
+    if let Some(final_size_histogram) = dataset.final_size_histogram {
+        arrays.set_item(
+            "final_size_histogram",
+            final_size_histogram.into_pyarray(py),
+        )?;
+    }
+    if let Some(observations) = dataset.observations {
+        arrays.set_item("observations", observations.into_pyarray(py))?;
+    }
+
+    Ok(arrays)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_generator::PolicyKind;
+
+    fn request(mode: Mode) -> Request {
+        Request {
+            n_samples: 100,
+            n_workers: 1,
+            chunk_size: 10,
+            mode,
+            canonical: false,
+            dedup: None,
+            dead_end_depth: None,
+            observations: true,
+            policy: PolicyArgs::new(PolicyKind::Uniform, 1.0, 3),
+        }
+    }
+
+    #[test]
+    fn supported_problems() {
+        assert!(get_generate_fn(Problem::Schur, 3).is_some());
+        assert!(get_generate_fn(Problem::WeakSchur, 5).is_some());
+        assert!(get_generate_fn(Problem::Schur, 6).is_none());
+        assert!(get_generate_fn(Problem::VanDerWaerden, 2).is_none());
+
+        assert!(parse::<Problem>("problem", "weak-schur").is_ok());
+        assert!(parse::<Mode>("mode", "Trajectory").is_ok());
+        assert!(parse::<PolicyKind>("policy", "greedy").is_err());
+    }
+
+    #[test]
+    fn validate() {
+        assert!(request(Mode::Partial).validate().is_ok());
+
+        let mut empty = request(Mode::Partial);
+        empty.n_samples = 0;
+        assert!(empty.validate().is_err());
+
+        for temperature in [f64::NAN, -1.0, f64::INFINITY] {
+            let mut request = request(Mode::Partial);
+            request.policy.temperature = temperature;
+            assert!(request.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn generate_trajectories() {
+        let generate = get_generate_fn(Problem::Schur, 3).unwrap();
+        let dataset = generate(&request(Mode::Trajectory));
+
+        // Whole games, the last one possibly exceeding the samples.
+        let n_samples = dataset.sizes.len();
+        assert!(n_samples >= 100);
+        let game_ids = dataset.game_ids.unwrap();
+        let steps = dataset.steps.unwrap();
+        for index in 0..n_samples {
+            assert_eq!(steps[index], dataset.sizes[index]);
+            if steps[index] > 0 {
+                assert_eq!(game_ids[index], game_ids[index - 1]);
+            }
+        }
+        assert_eq!(dataset.observations.unwrap().shape()[0], n_samples);
+    }
+}
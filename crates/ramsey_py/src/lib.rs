@@ -0,0 +1,16 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+mod classes;
+mod generation;
+
+use pyo3::{create_exception, exceptions::PyValueError, prelude::*};
+
+create_exception!(ramsey_dl, PlayError, PyValueError);
+
+#[pymodule]
+fn ramsey_dl(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add("PlayError", module.py().get_type::<PlayError>())?;
+    module.add_function(wrap_pyfunction!(generation::generate_colorings, module)?)?;
+    classes::add_classes(module)
+}
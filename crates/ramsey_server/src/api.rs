@@ -0,0 +1,323 @@
+use crate::game::{Game, Problem, new_game};
+use ramsey_theory::PlayError;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{
+    collections::BTreeMap,
+    io::Read,
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+use tiny_http::{Header, Method, Request, Response};
+
+/// Largest accepted request body, in bytes.
+const MAX_BODY_SIZE: u64 = 4096;
+
+struct Session {
+    problem: Problem,
+    n_colors: usize,
+    game: Box<dyn Game>,
+}
+
+#[derive(Default)]
+pub struct Sessions {
+    sessions: Mutex<BTreeMap<u64, Session>>,
+    next_id: AtomicU64,
+}
+
+#[derive(Deserialize)]
+struct NewGame {
+    problem: Problem,
+    colors: usize,
+}
+
+#[derive(Deserialize)]
+struct Play {
+    color: usize,
+}
+
+#[derive(Serialize)]
+struct State {
+    id: u64,
+    problem: Problem,
+    colors: usize,
+    size: usize,
+    max_size: usize,
+    coloring: Vec<usize>,
+    legal_moves: Vec<usize>,
+}
+
+#[derive(Serialize)]
+struct LegalMoves {
+    legal_moves: Vec<usize>,
+}
+
+#[derive(Serialize)]
+struct ApiError {
+    #[serde(skip)]
+    status: u16,
+    error: &'static str,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: u16, error: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            error,
+            message: message.into(),
+        }
+    }
+
+    fn not_found() -> Self {
+        Self::new(404, "not_found", "no such route")
+    }
+
+    fn no_session(id: u64) -> Self {
+        Self::new(404, "no_session", format!("no game with id {id}"))
+    }
+}
+
+impl From<PlayError> for ApiError {
+    fn from(err: PlayError) -> Self {
+        let error = match err {
+            PlayError::InvalidColor => "invalid_color",
+            PlayError::LimitReached => "limit_reached",
+            PlayError::IllegalMove => "illegal_move",
+        };
+
+        Self::new(422, error, err.to_string())
+    }
+}
+
+type Reply = Result<(u16, Option<String>), ApiError>;
+
+fn json(status: u16, body: &impl Serialize) -> Reply {
+    Ok((status, Some(serde_json::to_string(body).unwrap())))
+}
+
+fn parse_body<T>(reader: &mut dyn Read) -> Result<T, ApiError>
+where
+    T: DeserializeOwned,
+{
+    let mut body = String::new();
+    reader
+        .take(MAX_BODY_SIZE + 1)
+        .read_to_string(&mut body)
+        .map_err(|err| ApiError::new(400, "bad_request", err.to_string()))?;
+    if body.len() as u64 > MAX_BODY_SIZE {
+        let message = format!("the body exceeds {MAX_BODY_SIZE} bytes");
+        return Err(ApiError::new(413, "payload_too_large", message));
+    }
+
+    serde_json::from_str(&body).map_err(|err| ApiError::new(400, "bad_request", err.to_string()))
+}
+
+fn state(id: u64, session: &Session) -> State {
+    let coloring = session.game.coloring();
+
+    State {
+        id,
+        problem: session.problem,
+        colors: session.n_colors,
+        size: coloring.len(),
+        max_size: session.game.max_size(),
+        coloring,
+        legal_moves: session.game.legal_moves(),
+    }
+}
+
+impl Sessions {
+    fn create(&self, body: &mut dyn Read) -> Reply {
+        let NewGame { problem, colors } = parse_body(body)?;
+        let game = new_game(problem, colors).ok_or_else(|| {
+            ApiError::new(
+                422,
+                "unsupported_problem",
+                format!("{problem:?} with {colors} colors is not supported"),
+            )
+        })?;
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let session = Session {
+            problem,
+            n_colors: colors,
+            game,
+        };
+        let state = state(id, &session);
+        self.sessions.lock().unwrap().insert(id, session);
+
+        json(201, &state)
+    }
+
+    fn with_session<T>(
+        &self,
+        id: u64,
+        f: impl FnOnce(&mut Session) -> Result<T, ApiError>,
+    ) -> Result<T, ApiError> {
+        let mut sessions = self.sessions.lock().unwrap();
+        let session = sessions.get_mut(&id).ok_or(ApiError::no_session(id))?;
+
+        f(session)
+    }
+
+    fn handle_session(
+        &self,
+        method: &Method,
+        id: u64,
+        action: Option<&str>,
+        body: &mut dyn Read,
+    ) -> Reply {
+        match (method, action) {
+            (Method::Get, None) => self.with_session(id, |session| json(200, &state(id, session))),
+            (Method::Delete, None) => match self.sessions.lock().unwrap().remove(&id) {
+                Some(_) => Ok((204, None)),
+                None => Err(ApiError::no_session(id)),
+            },
+            (Method::Get, Some("legal-moves")) => self.with_session(id, |session| {
+                let legal_moves = session.game.legal_moves();
+                json(200, &LegalMoves { legal_moves })
+            }),
+            (Method::Post, Some("play")) => {
+                let Play { color } = parse_body(body)?;
+                self.with_session(id, |session| {
+                    session.game.play(color)?;
+                    json(200, &state(id, session))
+                })
+            }
+            (Method::Post, Some("undo")) => self.with_session(id, |session| {
+                if !session.game.undo() {
+                    return Err(ApiError::new(409, "nothing_to_undo", "no move to undo"));
+                }
+                json(200, &state(id, session))
+            }),
+            _ => Err(ApiError::not_found()),
+        }
+    }
+
+    fn route(&self, method: &Method, url: &str, body: &mut dyn Read) -> Reply {
+        let path = url.split('?').next().unwrap();
+        let segments: Vec<_> = path.trim_matches('/').split('/').collect();
+
+        match segments.as_slice() {
+            ["games"] if *method == Method::Post => self.create(body),
+            ["games"] if *method == Method::Get => {
+                let sessions = self.sessions.lock().unwrap();
+                let states: Vec<_> = sessions
+                    .iter()
+                    .map(|(&id, session)| state(id, session))
+                    .collect();
+                json(200, &states)
+            }
+            ["games", id, action @ ..] if action.len() <= 1 => {
+                let id = id.parse().map_err(|_| ApiError::not_found())?;
+                self.handle_session(method, id, action.first().copied(), body)
+            }
+            _ => Err(ApiError::not_found()),
+        }
+    }
+
+    pub fn respond(&self, mut request: Request) {
+        let method = request.method().clone();
+        let url = request.url().to_owned();
+        let reply = self.route(&method, &url, request.as_reader());
+        let (status, body) = reply.unwrap_or_else(|err| {
+            let body = serde_json::to_string(&err).unwrap();
+            (err.status, Some(body))
+        });
+
+        let response = match body {
+            Some(body) => {
+                let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+                Response::from_string(body)
+                    .with_status_code(status)
+                    .with_header(content_type)
+                    .boxed()
+            }
+            None => Response::empty(status).boxed(),
+        };
+
+        // The client may be gone, there is nobody to report the error to.
+        let _ = request.respond(response);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn send(sessions: &Sessions, method: Method, path: &str, body: &str) -> (u16, Value) {
+        match sessions.route(&method, path, &mut body.as_bytes()) {
+            Ok((status, body)) => {
+                let body = body.map_or(Value::Null, |body| serde_json::from_str(&body).unwrap());
+                (status, body)
+            }
+            Err(err) => (err.status, serde_json::to_value(&err).unwrap()),
+        }
+    }
+
+    #[test]
+    fn create_play_undo() {
+        let sessions = Sessions::default();
+
+        let body = r#"{"problem": "schur", "colors": 3}"#;
+        let (status, state) = send(&sessions, Method::Post, "/games", body);
+        assert_eq!(status, 201);
+        assert_eq!(state["max_size"], 13);
+        assert_eq!(state["legal_moves"], serde_json::json!([0, 1, 2]));
+        let id = state["id"].as_u64().unwrap();
+        let path = |action: &str| format!("/games/{id}{action}");
+
+        let (status, state) = send(&sessions, Method::Post, &path("/play"), r#"{"color": 0}"#);
+        assert_eq!(status, 200);
+        assert_eq!(state["coloring"], serde_json::json!([0]));
+        assert_eq!(state["legal_moves"], serde_json::json!([1, 2]));
+
+        let (status, error) = send(&sessions, Method::Post, &path("/play"), r#"{"color": 0}"#);
+        assert_eq!(status, 422);
+        assert_eq!(error["error"], "illegal_move");
+
+        let (status, error) = send(&sessions, Method::Post, &path("/play"), r#"{"color": 3}"#);
+        assert_eq!(status, 422);
+        assert_eq!(error["error"], "invalid_color");
+
+        let (status, state) = send(&sessions, Method::Post, &path("/undo"), "");
+        assert_eq!(status, 200);
+        assert_eq!(state["size"], 0);
+
+        let (status, error) = send(&sessions, Method::Post, &path("/undo"), "");
+        assert_eq!(status, 409);
+        assert_eq!(error["error"], "nothing_to_undo");
+
+        let (status, _) = send(&sessions, Method::Delete, &path(""), "");
+        assert_eq!(status, 204);
+        let (status, error) = send(&sessions, Method::Get, &path(""), "");
+        assert_eq!(status, 404);
+        assert_eq!(error["error"], "no_session");
+    }
+
+    #[test]
+    fn invalid_requests() {
+        let sessions = Sessions::default();
+
+        let (status, error) = send(&sessions, Method::Post, "/games", "{");
+        assert_eq!(status, 400);
+        assert_eq!(error["error"], "bad_request");
+
+        let body = r#"{"problem": "schur", "colors": 9}"#;
+        let (status, error) = send(&sessions, Method::Post, "/games", body);
+        assert_eq!(status, 422);
+        assert_eq!(error["error"], "unsupported_problem");
+
+        let body = " ".repeat(MAX_BODY_SIZE as usize + 1);
+        let (status, error) = send(&sessions, Method::Post, "/games", &body);
+        assert_eq!(status, 413);
+        assert_eq!(error["error"], "payload_too_large");
+
+        let (status, error) = send(&sessions, Method::Get, "/players", "");
+        assert_eq!(status, 404);
+        assert_eq!(error["error"], "not_found");
+    }
+}
@@ -0,0 +1,102 @@
+use ramsey_theory::{
+    PlayError, SequenceColoring, SequenceProblem,
+    problems::{Schur, WeakSchur},
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Problem {
+    Schur,
+    WeakSchur,
+}
+
+/// A game of any problem, so that the sessions can be stored together.
+pub trait Game: Send {
+    fn play(&mut self, color: usize) -> Result<(), PlayError>;
+
+    /// Returns whether there was a move to undo.
+    fn undo(&mut self) -> bool;
+
+    fn legal_moves(&self) -> Vec<usize>;
+
+    fn coloring(&self) -> Vec<usize>;
+
+    fn max_size(&self) -> usize;
+}
+
+struct Session<P>
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    coloring: SequenceColoring<P>,
+    // The moves cannot be taken back, so the previous states are kept.
+    history: Vec<SequenceColoring<P>>,
+}
+
+impl<P> Game for Session<P>
+where
+    P: SequenceProblem + Send,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    fn play(&mut self, color: usize) -> Result<(), PlayError> {
+        let previous = self.coloring.clone();
+        self.coloring.play(color)?;
+        self.history.push(previous);
+
+        Ok(())
+    }
+
+    fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(previous) => {
+                self.coloring = previous;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn legal_moves(&self) -> Vec<usize> {
+        self.coloring.legal_moves()
+    }
+
+    fn coloring(&self) -> Vec<usize> {
+        self.coloring.clone().into_iter().collect()
+    }
+
+    fn max_size(&self) -> usize {
+        P::BOUND - 1
+    }
+}
+
+fn new_session<P>() -> Box<dyn Game>
+where
+    P: SequenceProblem + Send + 'static,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    Box::new(Session::<P> {
+        coloring: SequenceColoring::new(),
+        history: Vec::new(),
+    })
+}
+
+macro_rules! new_game {
+    ($params:ident, $problem:tt, { $($cases:literal)* }) => {
+        match $params {
+            $($cases => Some(new_session::<$problem<$cases>>()),)*
+            _ => None,
+        }
+    };
+}
+
+pub fn new_game(problem: Problem, n_colors: usize) -> Option<Box<dyn Game>> {
+    match problem {
+        Problem::Schur => new_game!(n_colors, Schur, { 2 3 4 5 }),
+        Problem::WeakSchur => new_game!(n_colors, WeakSchur, { 2 3 4 5 }),
+    }
+}
@@ -0,0 +1,73 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+//! Local HTTP server of games, every body is JSON.
+//!
+//! - `POST /games` with `{"problem": "schur", "colors": 4}` creates a game
+//! - `GET /games` lists the games
+//! - `GET /games/{id}` returns the coloring of a game
+//! - `DELETE /games/{id}` ends a game
+//! - `GET /games/{id}/legal-moves` lists the legal colors
+//! - `POST /games/{id}/play` with `{"color": 2}` colors the next number
+//! - `POST /games/{id}/undo` takes back the last move
+//!
+//! Errors are returned as `{"error": "illegal_move", "message": "illegal move"}`.
+
+mod api;
+mod game;
+
+use api::Sessions;
+use clap::{CommandFactory, Parser, error::ErrorKind};
+use std::thread;
+use tiny_http::Server;
+
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[arg(long)]
+    #[arg(default_value = "127.0.0.1")]
+    host: String,
+
+    #[arg(short, long)]
+    #[arg(default_value_t = 8080)]
+    port: u16,
+
+    #[arg(short, long)]
+    workers: Option<usize>,
+}
+
+fn main() {
+    #[cfg(debug_assertions)]
+    {
+        use std::env;
+
+        unsafe {
+            // SAFETY: Only the main thread is running
+            env::set_var("RUST_BACKTRACE", "1");
+        }
+    }
+
+    let cli = Cli::parse();
+
+    let server = Server::http((cli.host.as_str(), cli.port)).unwrap_or_else(|err| {
+        let message = format!("cannot listen on {}:{}: {err}", cli.host, cli.port);
+        Cli::command().error(ErrorKind::Io, message).exit()
+    });
+    let n_workers = cli
+        .workers
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, Into::into))
+        .max(1);
+    let sessions = Sessions::default();
+
+    eprintln!("Listening on http://{}:{}", cli.host, cli.port);
+
+    thread::scope(|scope| {
+        for _ in 0..n_workers {
+            scope.spawn(|| {
+                for request in server.incoming_requests() {
+                    sessions.respond(request);
+                }
+            });
+        }
+    });
+}
@@ -0,0 +1,154 @@
+//! CNF encoding of "is there a coloring of `1..=n` without monochromatic forbidden solution?".
+//!
+//! The variable of the number `x` and the color `c` is `(x - 1) * N_COLORS + c + 1`, as in the
+//! DIMACS format the literals are the variables or their opposites.
+
+use crate::{SequenceProblem, problems::ForbiddenSolutions, symmetry::SymmetryBreaking};
+use std::io::{self, Write};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CnfConfig {
+    /// Forbid the numbers to have several colors, redundant for the satisfiability.
+    pub at_most_one: bool,
+    /// Colors of the first numbers.
+    pub prefix: Vec<usize>,
+    pub symmetry: SymmetryBreaking,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Cnf {
+    pub n_variables: usize,
+    pub clauses: Vec<Vec<i32>>,
+}
+
+pub fn variable(x: usize, color: usize, n_colors: usize) -> i32 {
+    ((x - 1) * n_colors + color + 1) as i32
+}
+
+impl Cnf {
+    pub fn encode<P>(n: usize, config: &CnfConfig) -> Self
+    where
+        P: SequenceProblem + ForbiddenSolutions,
+    {
+        let n_colors = P::N_COLORS;
+        let mut clauses = Vec::new();
+
+        for x in 1..=n {
+            clauses.push(
+                (0..n_colors)
+                    .map(|color| variable(x, color, n_colors))
+                    .collect(),
+            );
+
+            if config.at_most_one {
+                for first in 0..n_colors {
+                    for second in first + 1..n_colors {
+                        clauses.push(vec![
+                            -variable(x, first, n_colors),
+                            -variable(x, second, n_colors),
+                        ]);
+                    }
+                }
+            }
+
+            // Each solution is encoded with its largest number.
+            P::for_each_solution(x, n, |others| {
+                if others.iter().all(|&y| y < x) {
+                    for color in 0..n_colors {
+                        let mut clause = vec![-variable(x, color, n_colors)];
+                        clause.extend(others.iter().map(|&y| -variable(y, color, n_colors)));
+                        clauses.push(clause);
+                    }
+                }
+            });
+        }
+
+        for (index, &color) in config.prefix.iter().enumerate() {
+            clauses.push(vec![variable(index + 1, color, n_colors)]);
+        }
+        clauses.extend(config.symmetry.clauses::<P>(n, n_colors));
+
+        Self {
+            n_variables: n * n_colors,
+            clauses,
+        }
+    }
+
+    pub fn write_dimacs(&self, mut writer: impl Write, comments: &[String]) -> io::Result<()> {
+        for comment in comments {
+            writeln!(writer, "c {comment}")?;
+        }
+        writeln!(writer, "p cnf {} {}", self.n_variables, self.clauses.len())?;
+
+        for clause in &self.clauses {
+            for literal in clause {
+                write!(writer, "{literal} ")?;
+            }
+            writeln!(writer, "0")?;
+        }
+
+        writer.flush()
+    }
+}
+
+/// Literals of the model printed by a SAT solver, from the `v` lines of the competition format
+/// or from the lines of literals of the solvers printing the model alone.
+pub fn parse_model(output: &str) -> Result<Vec<i32>, String> {
+    let mut literals = Vec::new();
+
+    for line in output.lines() {
+        let mut tokens = line.split_whitespace().peekable();
+        let status = match tokens.peek() {
+            None | Some(&"c") => continue,
+            Some(&"s") => tokens.nth(1).unwrap_or_default(),
+            Some(&"v") => {
+                tokens.next();
+                ""
+            }
+            Some(&token) => token,
+        };
+        match status {
+            "UNSATISFIABLE" | "UNSAT" => return Err("the solver found no model".to_owned()),
+            "SATISFIABLE" | "SAT" => continue,
+            _ => {}
+        }
+
+        for token in tokens {
+            let literal: i32 = token
+                .parse()
+                .map_err(|err| format!("invalid literal {token:?}: {err}"))?;
+            if literal != 0 {
+                literals.push(literal);
+            }
+        }
+    }
+
+    if literals.is_empty() {
+        return Err("no model found".to_owned());
+    }
+
+    Ok(literals)
+}
+
+/// Colors of `1..=n` in a model, the smallest true color of each number without the at most one
+/// clauses.
+pub fn decode(model: &[i32], n: usize, n_colors: usize) -> Result<Vec<usize>, String> {
+    let mut colors = vec![None; n];
+
+    for &literal in model {
+        let index = literal.unsigned_abs() as usize - 1;
+        let (x, color) = (index / n_colors + 1, index % n_colors);
+        if literal < 0 || x > n {
+            continue;
+        }
+
+        let previous = colors[x - 1].get_or_insert(color);
+        *previous = color.min(*previous);
+    }
+
+    colors
+        .into_iter()
+        .enumerate()
+        .map(|(index, color)| color.ok_or_else(|| format!("{} has no color", index + 1)))
+        .collect()
+}
@@ -1,40 +1,57 @@
 use super::sequence_coloring::SequenceColoring;
 use crate::assert_const_generics::*;
+use crate::policies::Policy;
 use crate::problems::SequenceProblem;
-use rand::Rng;
-use std::{ops::Deref, vec::IntoIter as VecIntoIter};
+use rand::{Rng, seq::SliceRandom};
+use std::{array, ops::Deref, vec::IntoIter as VecIntoIter};
 
 #[derive(Debug, Eq, PartialEq, Clone, Hash, Ord, PartialOrd)]
 pub struct Coloring<const N_COLORS: usize>(Vec<usize>);
 
 impl<const N_COLORS: usize> Coloring<N_COLORS> {
-    pub fn random<P, R>(rng: &mut R) -> Self
+    pub fn random<P, Pol, R>(policy: &Pol, rng: &mut R) -> Self
     where
-        R: Rng + ?Sized,
+        Pol: Policy<P> + ?Sized,
+        R: Rng,
         P: SequenceProblem,
         Assert<{ N_COLORS == P::N_COLORS }>: IsTrue,
         [(); P::BOUND]:,
         [(); P::N_COLORS]:,
     {
-        let mut coloring = SequenceColoring::<P>::new();
+        Self::random_from(SequenceColoring::<P>::new(), policy, rng)
+    }
 
-        while let Some(color) = coloring.random_move(rng) {
-            // if random_move returns Some(color) then it is a legal move so this should not panic
+    pub fn random_from<P, Pol, R>(
+        mut coloring: SequenceColoring<P>,
+        policy: &Pol,
+        rng: &mut R,
+    ) -> Self
+    where
+        Pol: Policy<P> + ?Sized,
+        R: Rng,
+        P: SequenceProblem,
+        Assert<{ N_COLORS == P::N_COLORS }>: IsTrue,
+        [(); P::BOUND]:,
+        [(); P::N_COLORS]:,
+    {
+        while let Some(color) = policy.choose(&coloring, rng) {
+            // a policy only chooses legal moves so this should not panic
             coloring.play(color).expect("Illegal move.");
         }
 
         Self::from(coloring)
     }
 
-    pub fn random_partial<P, R>(rng: &mut R) -> Self
+    pub fn random_partial<P, Pol, R>(policy: &Pol, rng: &mut R) -> Self
     where
-        R: Rng + ?Sized,
+        Pol: Policy<P> + ?Sized,
+        R: Rng,
         P: SequenceProblem,
         Assert<{ N_COLORS == P::N_COLORS }>: IsTrue,
         [(); P::BOUND]:,
         [(); P::N_COLORS]:,
     {
-        let mut coloring = Self::random::<P, _>(rng);
+        let mut coloring = Self::random::<P, _, _>(policy, rng);
 
         let size = rng.gen_range(0..coloring.len());
 
@@ -44,6 +61,10 @@ impl<const N_COLORS: usize> Coloring<N_COLORS> {
         coloring
     }
 
+    pub fn prefix(&self, size: usize) -> Self {
+        Self(self.0[..size].to_vec())
+    }
+
     pub fn truncate(&mut self, size: usize) {
         self.0.truncate(size);
     }
@@ -65,6 +86,29 @@ impl<const N_COLORS: usize> Coloring<N_COLORS> {
             *color = color_order[*color].unwrap();
         }
     }
+
+    pub fn permute_colors(&mut self, permutation: &[usize; N_COLORS]) {
+        let mut is_image = [false; N_COLORS];
+        for &color in permutation {
+            assert!(color < N_COLORS, "Invalid color in permutation.");
+            is_image[color] = true;
+        }
+        assert!(is_image.iter().all(|&b| b), "Not a permutation.");
+
+        for color in self.0.iter_mut() {
+            *color = permutation[*color];
+        }
+    }
+
+    pub fn shuffle_colors<R>(&mut self, rng: &mut R)
+    where
+        R: Rng + ?Sized,
+    {
+        let mut permutation = array::from_fn(|color| color);
+        permutation.shuffle(rng);
+
+        self.permute_colors(&permutation);
+    }
 }
 
 // do not implement DerefMut, otherwise the user could put an invalid color in the Vec
@@ -15,6 +15,16 @@ impl<const N_COLORS: usize> CompressedColoring<N_COLORS> {
         CompressedColors::MAX.ilog(N_COLORS as CompressedColors) as usize
     };
 
+    /// Colors packed as by `From<Coloring>`, possibly padded like in the datasets.
+    pub fn from_compressed(compressed: Vec<CompressedColors>, size: usize) -> Self {
+        assert!(
+            compressed.len() * Self::COLORS_PER_ELEM >= size,
+            "Not enough colors for the size."
+        );
+
+        Self { compressed, size }
+    }
+
     pub fn size(&self) -> usize {
         self.size
     }
@@ -71,6 +81,12 @@ impl<const N_COLORS: usize> From<Coloring<N_COLORS>> for CompressedColoring<N_CO
     }
 }
 
+impl<const N_COLORS: usize> From<CompressedColoring<N_COLORS>> for Coloring<N_COLORS> {
+    fn from(coloring: CompressedColoring<N_COLORS>) -> Self {
+        coloring.decompress().collect()
+    }
+}
+
 #[cfg(feature = "ndarray")]
 use ndarray::Array1;
 
@@ -1,6 +1,6 @@
 use crate::{Array2D, problems::SequenceProblem};
 use rand::{Rng, seq::SliceRandom};
-use std::marker::PhantomData;
+use std::{error::Error, fmt, marker::PhantomData};
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash, Ord, PartialOrd)]
 pub enum PlayError {
@@ -9,7 +9,21 @@ pub enum PlayError {
     IllegalMove,
 }
 
-#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+impl fmt::Display for PlayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::InvalidColor => "invalid color",
+            Self::LimitReached => "upper bound reached",
+            Self::IllegalMove => "illegal move",
+        };
+
+        f.write_str(message)
+    }
+}
+
+impl Error for PlayError {}
+
+#[derive(Debug, Eq, PartialEq, Hash)]
 pub struct SequenceColoring<P>
 where
     P: SequenceProblem,
@@ -47,6 +61,23 @@ where
         self.size == P::BOUND - 1
     }
 
+    #[inline]
+    pub const fn partition(&self) -> &Array2D<{ P::N_COLORS }, { P::BOUND }, bool> {
+        &self.partition
+    }
+
+    #[inline]
+    pub const fn possible(&self) -> &Array2D<{ P::N_COLORS }, { P::BOUND }, bool> {
+        &self.possible
+    }
+
+    pub fn possible_count(&self, color: usize) -> usize {
+        self.possible[color][self.size..]
+            .iter()
+            .filter(|&&possible| possible)
+            .count()
+    }
+
     pub fn play(&mut self, color: usize) -> Result<(), PlayError> {
         if color >= P::N_COLORS {
             return Err(PlayError::InvalidColor);
@@ -82,6 +113,23 @@ where
     }
 }
 
+// Derived `Clone` would needlessly require `P: Clone`.
+impl<P> Clone for SequenceColoring<P>
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    fn clone(&self) -> Self {
+        Self {
+            size: self.size,
+            partition: self.partition,
+            possible: self.possible,
+            _problem: PhantomData,
+        }
+    }
+}
+
 impl<P> Default for SequenceColoring<P>
 where
     P: SequenceProblem,
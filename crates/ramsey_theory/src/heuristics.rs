@@ -0,0 +1,99 @@
+use crate::{SequenceColoring, SequenceProblem};
+
+pub trait Heuristic<P>
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    fn score(&self, coloring: &SequenceColoring<P>, color: usize) -> f64;
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash, Ord, PartialOrd, Default)]
+pub struct RemainingPossible;
+
+impl<P> Heuristic<P> for RemainingPossible
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    fn score(&self, coloring: &SequenceColoring<P>, color: usize) -> f64 {
+        coloring.possible_count(color) as f64
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash, Ord, PartialOrd, Default)]
+pub struct LeastConstraining;
+
+impl<P> Heuristic<P> for LeastConstraining
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    fn score(&self, coloring: &SequenceColoring<P>, color: usize) -> f64 {
+        let mut next = coloring.clone();
+        next.play(color).expect("Illegal move.");
+
+        (0..P::N_COLORS)
+            .map(|color| next.possible_count(color))
+            .sum::<usize>() as f64
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash, Ord, PartialOrd, Default)]
+pub struct MostConstrained;
+
+impl<P> Heuristic<P> for MostConstrained
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    fn score(&self, coloring: &SequenceColoring<P>, color: usize) -> f64 {
+        -(coloring.possible_count(color) as f64)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash, Ord, PartialOrd)]
+pub struct Lookahead {
+    pub depth: usize,
+}
+
+impl Lookahead {
+    fn reachable_depth<P>(coloring: &SequenceColoring<P>, depth: usize) -> usize
+    where
+        P: SequenceProblem,
+        [(); P::BOUND]:,
+        [(); P::N_COLORS]:,
+    {
+        let mut max_depth = 0;
+
+        for color in coloring.legal_moves() {
+            if max_depth == depth {
+                break;
+            }
+
+            let mut next = coloring.clone();
+            next.play(color).expect("Illegal move.");
+            max_depth = max_depth.max(1 + Self::reachable_depth(&next, depth - 1));
+        }
+
+        max_depth
+    }
+}
+
+impl<P> Heuristic<P> for Lookahead
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    fn score(&self, coloring: &SequenceColoring<P>, color: usize) -> f64 {
+        let mut next = coloring.clone();
+        next.play(color).expect("Illegal move.");
+
+        Self::reachable_depth(&next, self.depth.saturating_sub(1)) as f64
+    }
+}
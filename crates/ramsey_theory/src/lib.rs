@@ -2,8 +2,19 @@
 #![feature(generic_const_exprs)]
 
 pub mod assert_const_generics;
+pub mod cnf;
 pub mod colorings;
+pub mod heuristics;
+#[cfg(feature = "ndarray")]
+pub mod observation;
+pub mod policies;
 pub mod problems;
+#[cfg(feature = "sat")]
+pub mod sat;
+pub mod search;
+pub mod symmetry;
+#[cfg(feature = "ndarray")]
+pub mod vec_env;
 
 pub type Array2D<const N_ROWS: usize, const N_COLUMNS: usize, T> = [[T; N_COLUMNS]; N_ROWS];
 
@@ -12,4 +23,9 @@ pub use colorings::{
     compressed_coloring::{CompressedColoring, CompressedColors},
     sequence_coloring::{PlayError, SequenceColoring},
 };
+pub use heuristics::Heuristic;
+pub use policies::Policy;
 pub use problems::SequenceProblem;
+pub use search::Evaluator;
+#[cfg(feature = "ndarray")]
+pub use vec_env::VecEnv;
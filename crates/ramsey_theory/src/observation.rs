@@ -0,0 +1,76 @@
+//! Tensor encoding of the colorings, shared by the datasets and the inference.
+//!
+//! An observation has `2 * N_COLORS + 1` channels over the numbers `1..BOUND`:
+//! - the partition planes, the one-hot colors of the colored numbers,
+//! - the possible planes, the colors still legal for the uncolored numbers,
+//! - the position channel, one at the next number to color.
+
+use crate::{SequenceColoring, SequenceProblem};
+use ndarray::{Array2, Array3, ArrayViewMut2, Axis};
+
+pub const fn n_channels<P>() -> usize
+where
+    P: SequenceProblem,
+{
+    2 * P::N_COLORS + 1
+}
+
+pub const fn shape<P>() -> (usize, usize)
+where
+    P: SequenceProblem,
+{
+    (n_channels::<P>(), P::BOUND - 1)
+}
+
+fn fill<P>(coloring: &SequenceColoring<P>, mut observation: ArrayViewMut2<f32>)
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let size = coloring.size();
+
+    for color in 0..P::N_COLORS {
+        let partition = &coloring.partition()[color];
+        let possible = &coloring.possible()[color];
+
+        for num in 0..P::BOUND - 1 {
+            observation[[color, num]] = f32::from(u8::from(partition[num]));
+            observation[[P::N_COLORS + color, num]] =
+                f32::from(u8::from(num >= size && possible[num]));
+        }
+    }
+
+    if size < P::BOUND - 1 {
+        observation[[2 * P::N_COLORS, size]] = 1.0;
+    }
+}
+
+pub fn observation<P>(coloring: &SequenceColoring<P>) -> Array2<f32>
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let mut observation = Array2::zeros(shape::<P>());
+    fill(coloring, observation.view_mut());
+
+    observation
+}
+
+pub fn observations<P>(colorings: &[SequenceColoring<P>]) -> Array3<f32>
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let (n_channels, len) = shape::<P>();
+    let mut observations = Array3::zeros((colorings.len(), n_channels, len));
+
+    colorings
+        .iter()
+        .zip(observations.axis_iter_mut(Axis(0)))
+        .for_each(|(coloring, observation)| fill(coloring, observation));
+
+    observations
+}
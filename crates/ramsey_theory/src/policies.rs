@@ -0,0 +1,248 @@
+#[cfg(feature = "external")]
+pub mod external;
+#[cfg(feature = "onnx")]
+pub mod onnx;
+
+use crate::{SequenceColoring, SequenceProblem, heuristics::Heuristic};
+use rand::{
+    RngCore,
+    distributions::{Distribution, WeightedIndex},
+};
+
+pub trait Policy<P>
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    /// Probability of each color, zero for the illegal moves.
+    fn distribution(&self, coloring: &SequenceColoring<P>) -> Vec<f64>;
+
+    /// Distributions of a batch of colorings, for the policies evaluating states together.
+    fn distributions(&self, colorings: &[SequenceColoring<P>]) -> Vec<Vec<f64>> {
+        colorings
+            .iter()
+            .map(|coloring| self.distribution(coloring))
+            .collect()
+    }
+
+    /// Samples a move from the distribution, `None` if there is no legal move.
+    ///
+    /// Panics if the distribution is not a distribution over the legal moves.
+    fn choose(&self, coloring: &SequenceColoring<P>, rng: &mut dyn RngCore) -> Option<usize> {
+        let moves = coloring.legal_moves();
+        if moves.is_empty() {
+            return None;
+        }
+
+        let distribution = self.distribution(coloring);
+        assert_eq!(
+            distribution.len(),
+            P::N_COLORS,
+            "The distribution does not have a probability for each color."
+        );
+        let color = WeightedIndex::new(&distribution)
+            .unwrap_or_else(|err| panic!("Invalid distribution {distribution:?}: {err}."))
+            .sample(rng);
+        assert!(
+            moves.contains(&color),
+            "The distribution {distribution:?} has an illegal move."
+        );
+
+        Some(color)
+    }
+}
+
+fn uniform_over<P>(moves: &[usize]) -> Vec<f64>
+where
+    P: SequenceProblem,
+{
+    let mut distribution = vec![0.0; P::N_COLORS];
+
+    for &color in moves {
+        distribution[color] = 1.0 / moves.len() as f64;
+    }
+
+    distribution
+}
+
+/// Softmax of the logits of the colors over the legal moves, as output by a model.
+///
+/// A NaN or infinite logit of a legal move makes the distribution uniform over the legal moves,
+/// while the logits of the illegal moves are ignored.
+pub fn legal_softmax<P>(coloring: &SequenceColoring<P>, logits: &[f32]) -> Vec<f64>
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let moves = coloring.legal_moves();
+
+    let Some(max_logit) = moves
+        .iter()
+        .map(|&color| f64::from(logits[color]))
+        .reduce(f64::max)
+    else {
+        return vec![0.0; P::N_COLORS];
+    };
+    // `f64::max` ignores the NaN logits.
+    let is_nan = moves.iter().any(|&color| logits[color].is_nan());
+    if is_nan || !max_logit.is_finite() {
+        return uniform_over::<P>(&moves);
+    }
+
+    let mut distribution = vec![0.0; P::N_COLORS];
+    for &color in &moves {
+        distribution[color] = (f64::from(logits[color]) - max_logit).exp();
+    }
+
+    let total: f64 = distribution.iter().sum();
+    distribution
+        .iter_mut()
+        .for_each(|probability| *probability /= total);
+
+    distribution
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash, Ord, PartialOrd, Default)]
+pub struct UniformRandom;
+
+impl<P> Policy<P> for UniformRandom
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    fn distribution(&self, coloring: &SequenceColoring<P>) -> Vec<f64> {
+        uniform_over::<P>(&coloring.legal_moves())
+    }
+
+    fn choose(&self, coloring: &SequenceColoring<P>, mut rng: &mut dyn RngCore) -> Option<usize> {
+        coloring.random_move(&mut rng)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash, Ord, PartialOrd, Default)]
+pub struct FirstLegal;
+
+impl<P> Policy<P> for FirstLegal
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    fn distribution(&self, coloring: &SequenceColoring<P>) -> Vec<f64> {
+        let first_move = coloring.legal_moves().first().copied();
+        uniform_over::<P>(first_move.as_slice())
+    }
+}
+
+/// Softmax over the heuristic scores of the legal moves.
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub struct Boltzmann<H> {
+    pub heuristic: H,
+    /// At zero temperature, the best moves are chosen uniformly.
+    pub temperature: f64,
+}
+
+impl<H> Boltzmann<H> {
+    pub const fn new(heuristic: H, temperature: f64) -> Self {
+        Self {
+            heuristic,
+            temperature,
+        }
+    }
+
+    pub const fn greedy(heuristic: H) -> Self {
+        Self::new(heuristic, 0.0)
+    }
+}
+
+impl<P, H> Policy<P> for Boltzmann<H>
+where
+    P: SequenceProblem,
+    H: Heuristic<P>,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    fn distribution(&self, coloring: &SequenceColoring<P>) -> Vec<f64> {
+        let moves = coloring.legal_moves();
+        let scores: Vec<_> = moves
+            .iter()
+            .map(|&color| self.heuristic.score(coloring, color))
+            .collect();
+
+        let Some(max_score) = scores.iter().copied().reduce(f64::max) else {
+            return vec![0.0; P::N_COLORS];
+        };
+
+        if self.temperature <= 0.0 {
+            let best_moves: Vec<_> = moves
+                .iter()
+                .zip(&scores)
+                .filter(|&(_, &score)| score == max_score)
+                .map(|(&color, _)| color)
+                .collect();
+
+            return uniform_over::<P>(&best_moves);
+        }
+
+        let weights: Vec<_> = scores
+            .iter()
+            .map(|score| ((score - max_score) / self.temperature).exp())
+            .collect();
+        let total_weight: f64 = weights.iter().sum();
+
+        let mut distribution = vec![0.0; P::N_COLORS];
+        for (color, weight) in moves.into_iter().zip(weights) {
+            distribution[color] = weight / total_weight;
+        }
+
+        distribution
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problems::Schur;
+
+    #[test]
+    fn legal_softmax_masks_the_illegal_moves() {
+        let mut coloring = SequenceColoring::<Schur<3>>::new();
+        coloring.play(0).unwrap();
+
+        // 2 cannot have the color of 1, whatever its logit.
+        let distribution = legal_softmax(&coloring, &[100.0, 0.0, 0.0]);
+        assert_eq!(distribution, [0.0, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn legal_softmax_non_finite_logits() {
+        let coloring = SequenceColoring::<Schur<3>>::new();
+
+        for logits in [
+            [f32::NAN, 0.0, 1.0],
+            [f32::NAN; 3],
+            [f32::INFINITY, 0.0, 1.0],
+            [f32::NEG_INFINITY; 3],
+        ] {
+            assert_eq!(legal_softmax(&coloring, &logits), [1.0 / 3.0; 3]);
+        }
+
+        let distribution = legal_softmax(&coloring, &[f32::NEG_INFINITY, 0.0, 0.0]);
+        assert_eq!(distribution, [0.0, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn choose_without_legal_move() {
+        let mut coloring = SequenceColoring::<Schur<2>>::new();
+        for color in [0, 1, 1, 0] {
+            coloring.play(color).unwrap();
+        }
+        let mut rng = rand::thread_rng();
+
+        assert_eq!(UniformRandom.choose(&coloring, &mut rng), None);
+        assert_eq!(FirstLegal.choose(&coloring, &mut rng), None);
+    }
+}
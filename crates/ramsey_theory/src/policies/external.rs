@@ -0,0 +1,292 @@
+//! Policy evaluated by a subprocess, typically a Python model.
+//!
+//! The protocol is line based JSON. For each batch of states, one request is written
+//! to the stdin of the process:
+//!
+//! ```json
+//! {"n_colors": 3, "max_size": 13, "colorings": [[0, 1, 1], []]}
+//! ```
+//!
+//! and one response is read from its stdout, with a probability per color for each
+//! coloring of the request:
+//!
+//! ```json
+//! {"probabilities": [[0.2, 0.3, 0.5], [1.0, 0.0, 0.0]]}
+//! ```
+//!
+//! The probabilities of the illegal moves are ignored and the others renormalized.
+//! The process should exit when its stdin is closed, it is killed otherwise. A uniform
+//! stand-in is:
+//!
+//! ```python
+//! import json, sys
+//!
+//! for line in sys.stdin:
+//!     request = json.loads(line)
+//!     n_colors = request["n_colors"]
+//!     uniform = [1 / n_colors] * n_colors
+//!     response = {"probabilities": [uniform for _ in request["colorings"]]}
+//!     print(json.dumps(response), flush=True)
+//! ```
+
+use super::{Policy, uniform_over};
+use crate::{SequenceColoring, SequenceProblem};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+    sync::Mutex,
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Time given to the process to exit once its stdin is closed.
+const EXIT_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Serialize)]
+struct Request {
+    n_colors: usize,
+    max_size: usize,
+    colorings: Vec<Vec<usize>>,
+}
+
+#[derive(Deserialize)]
+struct Response {
+    probabilities: Vec<Vec<f64>>,
+}
+
+struct Process {
+    child: Child,
+    // Taken when dropped, closing the pipe tells the process to exit.
+    stdin: Option<ChildStdin>,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl Process {
+    fn query(&mut self, request: &Request) -> io::Result<Response> {
+        let stdin = self.stdin.as_mut().unwrap();
+        serde_json::to_writer(&mut *stdin, request)?;
+        stdin.write_all(b"\n")?;
+        stdin.flush()?;
+
+        let mut line = String::new();
+        if self.stdout.read_line(&mut line)? == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "the external policy closed its stdout",
+            ));
+        }
+
+        Ok(serde_json::from_str(&line)?)
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        drop(self.stdin.take());
+
+        let deadline = Instant::now() + EXIT_TIMEOUT;
+        while Instant::now() < deadline {
+            match self.child.try_wait() {
+                Ok(None) => thread::sleep(Duration::from_millis(10)),
+                Ok(Some(_)) | Err(_) => return,
+            }
+        }
+
+        // The process ignores the end of its stdin.
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+pub struct ExternalPolicy {
+    process: Mutex<Process>,
+}
+
+impl ExternalPolicy {
+    /// Spawns the command with piped stdin and stdout, stderr is inherited.
+    pub fn spawn(command: &mut Command) -> io::Result<Self> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        let stdin = child.stdin.take();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+
+        Ok(Self {
+            process: Mutex::new(Process {
+                child,
+                stdin,
+                stdout,
+            }),
+        })
+    }
+
+    pub fn query<P>(&self, colorings: &[SequenceColoring<P>]) -> io::Result<Vec<Vec<f64>>>
+    where
+        P: SequenceProblem,
+        [(); P::BOUND]:,
+        [(); P::N_COLORS]:,
+    {
+        let request = Request {
+            n_colors: P::N_COLORS,
+            max_size: P::BOUND - 1,
+            colorings: colorings
+                .iter()
+                .map(|coloring| coloring.clone().into_iter().collect())
+                .collect(),
+        };
+
+        let response = self.process.lock().unwrap().query(&request)?;
+
+        if response.probabilities.len() != colorings.len()
+            || response
+                .probabilities
+                .iter()
+                .any(|probabilities| probabilities.len() != P::N_COLORS)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the external policy answered with the wrong number of probabilities",
+            ));
+        }
+
+        Ok(response.probabilities)
+    }
+
+    /// Distributions of a batch of colorings, like `Policy::distributions` which panics on the
+    /// errors of the process.
+    pub fn try_distributions<P>(
+        &self,
+        colorings: &[SequenceColoring<P>],
+    ) -> io::Result<Vec<Vec<f64>>>
+    where
+        P: SequenceProblem,
+        [(); P::BOUND]:,
+        [(); P::N_COLORS]:,
+    {
+        let probabilities = self.query(colorings)?;
+
+        Ok(colorings
+            .iter()
+            .zip(probabilities)
+            .map(|(coloring, probabilities)| legal_distribution(coloring, probabilities))
+            .collect())
+    }
+}
+
+fn legal_distribution<P>(coloring: &SequenceColoring<P>, probabilities: Vec<f64>) -> Vec<f64>
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let moves = coloring.legal_moves();
+
+    let mut distribution = vec![0.0; P::N_COLORS];
+    for &color in &moves {
+        distribution[color] = probabilities[color].max(0.0);
+    }
+
+    let total: f64 = distribution.iter().sum();
+    if total > 0.0 && total.is_finite() {
+        distribution
+            .iter_mut()
+            .for_each(|probability| *probability /= total);
+        distribution
+    } else {
+        // The model gives no mass to the legal moves.
+        uniform_over::<P>(&moves)
+    }
+}
+
+impl<P> Policy<P> for ExternalPolicy
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    fn distribution(&self, coloring: &SequenceColoring<P>) -> Vec<f64> {
+        self.distributions(std::slice::from_ref(coloring))
+            .pop()
+            .unwrap()
+    }
+
+    fn distributions(&self, colorings: &[SequenceColoring<P>]) -> Vec<Vec<f64>> {
+        self.try_distributions(colorings)
+            .unwrap_or_else(|err| panic!("The external policy failed: {err}."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Coloring, problems::Schur};
+    use rand::{SeedableRng, rngs::StdRng};
+
+    /// The uniform stand-in of the documentation.
+    const UNIFORM: &str = r#"
+import json, sys
+
+for line in sys.stdin:
+    request = json.loads(line)
+    n_colors = request["n_colors"]
+    uniform = [1 / n_colors] * n_colors
+    response = {"probabilities": [uniform for _ in request["colorings"]]}
+    print(json.dumps(response), flush=True)
+"#;
+
+    fn spawn(command: &str, args: &[&str]) -> ExternalPolicy {
+        ExternalPolicy::spawn(Command::new("sh").arg("-c").arg(command).args(args))
+            .expect("Cannot start the shell.")
+    }
+
+    #[test]
+    fn uniform_stand_in() {
+        let policy = spawn(r#"exec python3 -c "$0""#, &[UNIFORM]);
+
+        let mut coloring = SequenceColoring::<Schur<3>>::new();
+        coloring.play(0).unwrap();
+        let distributions = policy
+            .try_distributions(&[SequenceColoring::new(), coloring])
+            .unwrap();
+        assert_eq!(distributions[0], [1.0 / 3.0; 3]);
+        // 2 cannot have the color of 1.
+        assert_eq!(distributions[1], [0.0, 0.5, 0.5]);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let coloring = Coloring::<3>::random::<Schur<3>, _, _>(&policy, &mut rng);
+        assert!(!coloring.is_empty());
+    }
+
+    #[test]
+    fn wrong_number_of_probabilities() {
+        let policy = spawn(r#"read line; echo '{"probabilities": [[1.0]]}'"#, &[]);
+
+        let err = policy
+            .try_distributions(&[SequenceColoring::<Schur<3>>::new()])
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn closed_stdout() {
+        let policy = spawn("exit 0", &[]);
+
+        assert!(
+            policy
+                .try_distributions(&[SequenceColoring::<Schur<3>>::new()])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn killed_when_ignoring_eof() {
+        let policy = spawn("exec sleep 60", &[]);
+
+        let start = Instant::now();
+        drop(policy);
+        assert!(start.elapsed() < EXIT_TIMEOUT + Duration::from_secs(5));
+    }
+}
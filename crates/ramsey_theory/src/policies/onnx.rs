@@ -0,0 +1,200 @@
+//! Policy evaluated by an ONNX model with the tract runtime, on CPU.
+//!
+//! The model takes one `f32` input of shape `[batch, channels, BOUND - 1]`, the
+//! observations of `crate::observation`, as written in the datasets. The first
+//! output holds the logits of the colors, of shape `[batch, N_COLORS]`, and an
+//! optional second output the values, of shape `[batch]` or `[batch, 1]`.
+
+use super::{Policy, legal_softmax};
+use crate::{SequenceColoring, SequenceProblem, observation, search::Evaluator};
+use rand::RngCore;
+use std::path::Path;
+use tract_onnx::prelude::*;
+
+type Model = SimplePlan<TypedFact, Box<dyn TypedOp>, Graph<TypedFact, Box<dyn TypedOp>>>;
+
+pub struct Prediction {
+    /// Softmax of the logits over the legal moves.
+    pub distributions: Vec<Vec<f64>>,
+    pub values: Option<Vec<f64>>,
+}
+
+pub struct OnnxPolicy {
+    model: Model,
+}
+
+impl OnnxPolicy {
+    pub fn load<P>(path: impl AsRef<Path>) -> TractResult<Self>
+    where
+        P: SequenceProblem,
+    {
+        let model = tract_onnx::onnx().model_for_path(path)?;
+        let batch = model.symbol_table.sym("batch");
+        let (n_channels, len) = observation::shape::<P>();
+        let input_fact = f32::fact([batch.to_dim(), n_channels.to_dim(), len.to_dim()]);
+
+        let model = model
+            .with_input_fact(0, input_fact.into())?
+            .into_optimized()?;
+
+        let logits_shape = &model.output_fact(0)?.shape;
+        if logits_shape.rank() != 2 || logits_shape[1] != P::N_COLORS.to_dim() {
+            return Err(TractError::msg(format!(
+                "the logits have the shape {logits_shape:?} instead of [batch, {}]",
+                P::N_COLORS
+            )));
+        }
+
+        Ok(Self {
+            model: model.into_runnable()?,
+        })
+    }
+
+    /// Whether the model has the value output, needed to evaluate colorings.
+    pub fn has_values(&self) -> bool {
+        self.model.model().outputs.len() > 1
+    }
+
+    pub fn predict<P>(&self, colorings: &[SequenceColoring<P>]) -> TractResult<Prediction>
+    where
+        P: SequenceProblem,
+        [(); P::BOUND]:,
+        [(); P::N_COLORS]:,
+    {
+        let observations = observation::observations(colorings);
+        // tract has its own version of ndarray.
+        let input = Tensor::from_shape(observations.shape(), observations.as_slice().unwrap())?;
+
+        let outputs = self.model.run(tvec!(input.into()))?;
+
+        let logits = outputs[0]
+            .to_array_view::<f32>()?
+            .into_shape((colorings.len(), P::N_COLORS))?;
+        let distributions = colorings
+            .iter()
+            .zip(logits.outer_iter())
+            .map(|(coloring, logits)| legal_softmax(coloring, logits.as_slice().unwrap()))
+            .collect();
+
+        let values = outputs
+            .get(1)
+            .map(|values| -> TractResult<_> {
+                let values = values.to_array_view::<f32>()?;
+                if values.len() != colorings.len() {
+                    return Err(TractError::msg(format!(
+                        "the model returns {} values for {} colorings",
+                        values.len(),
+                        colorings.len()
+                    )));
+                }
+                Ok(values.iter().map(|&value| f64::from(value)).collect())
+            })
+            .transpose()?;
+
+        Ok(Prediction {
+            distributions,
+            values,
+        })
+    }
+}
+
+impl<P> Policy<P> for OnnxPolicy
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    fn distribution(&self, coloring: &SequenceColoring<P>) -> Vec<f64> {
+        self.distributions(std::slice::from_ref(coloring))
+            .pop()
+            .unwrap()
+    }
+
+    fn distributions(&self, colorings: &[SequenceColoring<P>]) -> Vec<Vec<f64>> {
+        self.predict(colorings)
+            .expect("The ONNX model failed.")
+            .distributions
+    }
+}
+
+/// Needs the optional value output of the model.
+impl<P> Evaluator<P> for OnnxPolicy
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    fn value(&self, coloring: &SequenceColoring<P>, rng: &mut dyn RngCore) -> f64 {
+        self.values(std::slice::from_ref(coloring), rng)[0]
+    }
+
+    fn values(&self, colorings: &[SequenceColoring<P>], _rng: &mut dyn RngCore) -> Vec<f64> {
+        self.predict(colorings)
+            .expect("The ONNX model failed.")
+            .values
+            .expect("The ONNX model has no value output.")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problems::Schur;
+
+    /// Logits summing the possible planes and value summing all the channels, see the script
+    /// writing it.
+    const TINY: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/tiny.onnx");
+    const TINY_POLICY: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/tiny_policy.onnx"
+    );
+
+    #[test]
+    fn predict() {
+        let policy = OnnxPolicy::load::<Schur<3>>(TINY).unwrap();
+        assert!(policy.has_values());
+
+        let mut first = SequenceColoring::<Schur<3>>::new();
+        first.play(0).unwrap();
+        let mut second = first.clone();
+        second.play(1).unwrap();
+        let colorings = [SequenceColoring::new(), first, second];
+        let prediction = policy.predict(&colorings).unwrap();
+
+        for (coloring, distribution) in colorings.iter().zip(&prediction.distributions) {
+            let observation = observation::observation(coloring);
+            let logits: Vec<_> = observation
+                .outer_iter()
+                .skip(3)
+                .take(3)
+                .map(|possible| possible.sum())
+                .collect();
+            assert_eq!(*distribution, legal_softmax(coloring, &logits));
+        }
+        assert_eq!(prediction.distributions[0], [1.0 / 3.0; 3]);
+        // 2 cannot have the color of 1.
+        assert_eq!(prediction.distributions[1][0], 0.0);
+
+        let values = colorings
+            .iter()
+            .map(|coloring| f64::from(observation::observation(coloring).sum()))
+            .collect();
+        assert_eq!(prediction.values, Some(values));
+    }
+
+    #[test]
+    fn no_values() {
+        let policy = OnnxPolicy::load::<Schur<3>>(TINY_POLICY).unwrap();
+        assert!(!policy.has_values());
+
+        let prediction = policy
+            .predict(&[SequenceColoring::<Schur<3>>::new()])
+            .unwrap();
+        assert_eq!(prediction.values, None);
+    }
+
+    #[test]
+    fn wrong_problem() {
+        assert!(OnnxPolicy::load::<Schur<4>>(TINY).is_err());
+    }
+}
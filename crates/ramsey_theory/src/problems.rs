@@ -27,6 +27,36 @@ pub trait SequenceProblem: UpperBound {
     );
 }
 
+/// Sets of numbers which must not have a single color, for the searches on full colorings.
+///
+/// Only the Schur problems implement it, the van der Waerden progressions are not enumerated.
+pub trait ForbiddenSolutions {
+    /// Calls `f` once for each solution in `1..=n` containing `x`, with its other numbers.
+    fn for_each_solution(x: usize, n: usize, f: impl FnMut(&[usize]));
+
+    /// Colors of the first numbers in every coloring, up to a permutation of the colors.
+    fn canonical_start() -> Vec<usize> {
+        vec![0]
+    }
+
+    /// Number of monochromatic solutions, `colors[i]` being the color of `i + 1`.
+    fn monochromatic_count(colors: &[usize]) -> usize {
+        let mut count = 0;
+
+        for x in 1..=colors.len() {
+            let color = colors[x - 1];
+            // Each solution is counted with its largest number.
+            Self::for_each_solution(x, colors.len(), |others| {
+                if others.iter().all(|&y| y < x && colors[y - 1] == color) {
+                    count += 1;
+                }
+            });
+        }
+
+        count
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash, Ord, PartialOrd, Default)]
 pub struct Schur<const N_COLORS: usize>
 where
@@ -63,6 +93,34 @@ where
     }
 }
 
+/// The solutions of `x + y = z`, with `x = y` allowed.
+impl<const N_COLORS: usize> ForbiddenSolutions for Schur<N_COLORS>
+where
+    Self: UpperBound,
+{
+    /// 2 cannot have the color of 1 as 1 + 1 = 2.
+    fn canonical_start() -> Vec<usize> {
+        if N_COLORS > 1 { vec![0, 1] } else { vec![0] }
+    }
+
+    fn for_each_solution(x: usize, n: usize, mut f: impl FnMut(&[usize])) {
+        for a in 1..=x / 2 {
+            if 2 * a == x {
+                f(&[a]);
+            } else {
+                f(&[a, x - a]);
+            }
+        }
+        for y in 1..=n.saturating_sub(x) {
+            if y == x {
+                f(&[x + y]);
+            } else {
+                f(&[y, x + y]);
+            }
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash, Ord, PartialOrd, Default)]
 pub struct WeakSchur<const N_COLORS: usize>
 where
@@ -99,6 +157,21 @@ where
     }
 }
 
+/// The solutions of `x + y = z` with `x != y`.
+impl<const N_COLORS: usize> ForbiddenSolutions for WeakSchur<N_COLORS>
+where
+    Self: UpperBound,
+{
+    fn for_each_solution(x: usize, n: usize, mut f: impl FnMut(&[usize])) {
+        for a in 1..=(x - 1) / 2 {
+            f(&[a, x - a]);
+        }
+        for y in (1..=n.saturating_sub(x)).filter(|&y| y != x) {
+            f(&[y, x + y]);
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Copy, Clone, Hash, Ord, PartialOrd, Default)]
 pub struct VanDerWaerden<const N_COLORS: usize, const PROGRESSION_LEN: usize>
 where
@@ -123,8 +196,8 @@ upper_bound_impl! { VanDerWaerden<4, 3> = 75 }
 upper_bound_impl! { VanDerWaerden<5, 3> = 180 }
 upper_bound_impl! { VanDerWaerden<6, 3> = 242 }
 
-impl<const PROGRESSION_LEN: usize, const N_COLORS: usize> SequenceProblem
-    for VanDerWaerden<PROGRESSION_LEN, N_COLORS>
+impl<const N_COLORS: usize, const PROGRESSION_LEN: usize> SequenceProblem
+    for VanDerWaerden<N_COLORS, PROGRESSION_LEN>
 where
     Self: UpperBound,
 {
@@ -0,0 +1,427 @@
+//! Small CDCL SAT solver answering whether a prefix extends to a coloring of `1..=n`.
+//!
+//! Watched literals, learning of the first unique implication point, VSIDS-like activities,
+//! phase saving and Luby restarts.
+
+use crate::{
+    Coloring, SequenceProblem,
+    assert_const_generics::*,
+    cnf::{Cnf, CnfConfig, decode},
+    problems::ForbiddenSolutions,
+    symmetry::SymmetryBreaking,
+};
+use std::mem;
+
+/// The variable of the literal `2 * v + 1` is `v` and the one of `2 * v` is its opposite.
+type Literal = usize;
+
+fn literal(dimacs: i32) -> Literal {
+    2 * (dimacs.unsigned_abs() as usize - 1) + usize::from(dimacs > 0)
+}
+
+fn variable(literal: Literal) -> usize {
+    literal / 2
+}
+
+fn opposite(literal: Literal) -> Literal {
+    literal ^ 1
+}
+
+pub struct Solver {
+    clauses: Vec<Vec<Literal>>,
+    /// Clauses of which each literal is one of the first two, visited when it becomes false.
+    watches: Vec<Vec<usize>>,
+    values: Vec<Option<bool>>,
+    levels: Vec<usize>,
+    reasons: Vec<Option<usize>>,
+    trail: Vec<Literal>,
+    level_starts: Vec<usize>,
+    propagated: usize,
+    activities: Vec<f64>,
+    increment: f64,
+    phases: Vec<bool>,
+    /// An empty clause or opposite unit clauses.
+    trivially_unsat: bool,
+}
+
+impl Solver {
+    pub fn new(cnf: &Cnf) -> Self {
+        let n_variables = cnf.n_variables;
+        let mut solver = Self {
+            clauses: Vec::new(),
+            watches: vec![Vec::new(); 2 * n_variables],
+            values: vec![None; n_variables],
+            levels: vec![0; n_variables],
+            reasons: vec![None; n_variables],
+            trail: Vec::new(),
+            level_starts: Vec::new(),
+            propagated: 0,
+            activities: vec![0.0; n_variables],
+            increment: 1.0,
+            phases: vec![false; n_variables],
+            trivially_unsat: false,
+        };
+
+        for clause in &cnf.clauses {
+            let mut clause: Vec<_> = clause.iter().map(|&dimacs| literal(dimacs)).collect();
+            clause.sort_unstable();
+            clause.dedup();
+            if clause.windows(2).any(|pair| pair[1] == opposite(pair[0])) {
+                continue;
+            }
+
+            match clause[..] {
+                [] => solver.trivially_unsat = true,
+                [unit] => match solver.value(unit) {
+                    Some(false) => solver.trivially_unsat = true,
+                    Some(true) => {}
+                    None => solver.assign(unit, None),
+                },
+                _ => {
+                    solver.add_clause(clause);
+                }
+            }
+        }
+
+        solver
+    }
+
+    fn value(&self, literal: Literal) -> Option<bool> {
+        self.values[variable(literal)].map(|value| value == (literal % 2 == 1))
+    }
+
+    fn level(&self) -> usize {
+        self.level_starts.len()
+    }
+
+    fn assign(&mut self, literal: Literal, reason: Option<usize>) {
+        let variable = variable(literal);
+        self.values[variable] = Some(literal % 2 == 1);
+        self.levels[variable] = self.level();
+        self.reasons[variable] = reason;
+        self.trail.push(literal);
+    }
+
+    fn add_clause(&mut self, clause: Vec<Literal>) -> usize {
+        let index = self.clauses.len();
+        self.watches[clause[0]].push(index);
+        self.watches[clause[1]].push(index);
+        self.clauses.push(clause);
+
+        index
+    }
+
+    /// Returns the clause whose literals are all false, if any.
+    fn propagate(&mut self) -> Option<usize> {
+        while self.propagated < self.trail.len() {
+            let false_literal = opposite(self.trail[self.propagated]);
+            self.propagated += 1;
+
+            let mut watchers = mem::take(&mut self.watches[false_literal]);
+            let mut index = 0;
+            while index < watchers.len() {
+                let clause_index = watchers[index];
+                let clause = &mut self.clauses[clause_index];
+                if clause[0] == false_literal {
+                    clause.swap(0, 1);
+                }
+
+                let first = clause[0];
+                if self.values[variable(first)] == Some(first % 2 == 1) {
+                    index += 1;
+                    continue;
+                }
+
+                let replacement = (2..clause.len()).find(|&position| {
+                    let literal = clause[position];
+                    self.values[variable(literal)] != Some(literal % 2 == 0)
+                });
+                if let Some(position) = replacement {
+                    clause.swap(1, position);
+                    self.watches[clause[1]].push(clause_index);
+                    watchers.swap_remove(index);
+                    continue;
+                }
+
+                if self.value(first) == Some(false) {
+                    self.watches[false_literal] = watchers;
+                    return Some(clause_index);
+                }
+                self.assign(first, Some(clause_index));
+                index += 1;
+            }
+
+            self.watches[false_literal] = watchers;
+        }
+
+        None
+    }
+
+    fn bump(&mut self, variable: usize) {
+        self.activities[variable] += self.increment;
+        if self.activities[variable] > 1e100 {
+            self.activities
+                .iter_mut()
+                .for_each(|activity| *activity *= 1e-100);
+            self.increment *= 1e-100;
+        }
+    }
+
+    /// The clause learnt from a conflict, implying its first literal after the backjump to the
+    /// level of its second one.
+    fn analyze(&mut self, mut conflict: usize) -> Vec<Literal> {
+        let mut seen = vec![false; self.values.len()];
+        let mut learnt = vec![0];
+        let mut at_level = 0;
+        let mut index = self.trail.len();
+        let mut implied = None;
+
+        loop {
+            // The first literal of a reason is the one it implied.
+            let skipped = usize::from(implied.is_some());
+            for position in skipped..self.clauses[conflict].len() {
+                let literal = self.clauses[conflict][position];
+                let variable = variable(literal);
+                if seen[variable] || self.levels[variable] == 0 {
+                    continue;
+                }
+
+                seen[variable] = true;
+                self.bump(variable);
+                if self.levels[variable] == self.level() {
+                    at_level += 1;
+                } else {
+                    learnt.push(literal);
+                }
+            }
+
+            let literal = loop {
+                index -= 1;
+                if seen[variable(self.trail[index])] {
+                    break self.trail[index];
+                }
+            };
+            seen[variable(literal)] = false;
+            at_level -= 1;
+            if at_level == 0 {
+                learnt[0] = opposite(literal);
+                break;
+            }
+
+            implied = Some(literal);
+            conflict = self.reasons[variable(literal)].unwrap();
+        }
+
+        if let Some(position) = (1..learnt.len()).max_by_key(|&p| self.levels[variable(learnt[p])])
+        {
+            learnt.swap(1, position);
+        }
+
+        learnt
+    }
+
+    fn backjump(&mut self, level: usize) {
+        if level >= self.level() {
+            return;
+        }
+
+        let start = self.level_starts[level];
+        for literal in self.trail.drain(start..) {
+            let variable = variable(literal);
+            self.phases[variable] = literal % 2 == 1;
+            self.values[variable] = None;
+            self.reasons[variable] = None;
+        }
+        self.level_starts.truncate(level);
+        self.propagated = start;
+    }
+
+    /// The value of each variable in a model, `None` without one.
+    pub fn solve(mut self) -> Option<Vec<bool>> {
+        if self.trivially_unsat {
+            return None;
+        }
+
+        let mut conflicts = 0;
+        let mut restart = 1;
+        let mut restart_limit = 100 * luby(restart);
+
+        loop {
+            if let Some(conflict) = self.propagate() {
+                if self.level() == 0 {
+                    return None;
+                }
+
+                let learnt = self.analyze(conflict);
+                let level = learnt
+                    .get(1)
+                    .map_or(0, |&literal| self.levels[variable(literal)]);
+                self.backjump(level);
+                if learnt.len() == 1 {
+                    self.assign(learnt[0], None);
+                } else {
+                    let implied = learnt[0];
+                    let reason = self.add_clause(learnt);
+                    self.assign(implied, Some(reason));
+                }
+                self.increment /= 0.95;
+
+                conflicts += 1;
+                if conflicts >= restart_limit {
+                    conflicts = 0;
+                    restart += 1;
+                    restart_limit = 100 * luby(restart);
+                    self.backjump(0);
+                }
+                continue;
+            }
+
+            let unassigned = (0..self.values.len())
+                .filter(|&variable| self.values[variable].is_none())
+                .max_by(|&a, &b| self.activities[a].total_cmp(&self.activities[b]));
+            let Some(variable) = unassigned else {
+                return Some(self.values.into_iter().map(Option::unwrap).collect());
+            };
+
+            self.level_starts.push(self.trail.len());
+            self.assign(2 * variable + usize::from(self.phases[variable]), None);
+        }
+    }
+}
+
+/// The `index`th term of 1, 1, 2, 1, 1, 2, 4, 1, 1, 2..., starting at 1.
+fn luby(mut index: usize) -> usize {
+    loop {
+        let mut k = 1;
+        while (1 << k) - 1 < index {
+            k += 1;
+        }
+        if index == (1 << k) - 1 {
+            return 1 << (k - 1);
+        }
+        index -= (1 << (k - 1)) - 1;
+    }
+}
+
+/// A coloring of `1..=n` starting with `prefix`, `None` if there is none.
+///
+/// The prefix must be allowed by the symmetry breaking, see `SymmetryBreaking::allows_all`.
+// Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+#[allow(clippy::eq_op)]
+pub fn complete<P>(
+    n: usize,
+    prefix: &[usize],
+    symmetry: SymmetryBreaking,
+) -> Option<Coloring<{ P::N_COLORS }>>
+where
+    P: SequenceProblem + ForbiddenSolutions,
+    Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    // Without them, the decoding could pick another true color than the one of the prefix.
+    let config = CnfConfig {
+        at_most_one: true,
+        prefix: prefix.to_vec(),
+        symmetry,
+    };
+    let model = Solver::new(&Cnf::encode::<P>(n, &config)).solve()?;
+
+    let literals: Vec<_> = (1..=model.len() as i32)
+        .map(|variable| {
+            if model[variable as usize - 1] {
+                variable
+            } else {
+                -variable
+            }
+        })
+        .collect();
+    let colors = decode(&literals, n, P::N_COLORS).expect("The model leaves a number uncolored.");
+    assert_eq!(
+        P::monochromatic_count(&colors),
+        0,
+        "The model has a monochromatic solution."
+    );
+
+    Some(colors.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        problems::{Schur, WeakSchur},
+        search::dfs::Dfs,
+    };
+
+    const BROKEN: SymmetryBreaking = SymmetryBreaking {
+        first_appearance: true,
+        fixed_start: true,
+    };
+
+    #[test]
+    fn schur_number() {
+        assert!(complete::<Schur<2>>(4, &[], SymmetryBreaking::default()).is_some());
+        assert!(complete::<Schur<2>>(5, &[], SymmetryBreaking::default()).is_none());
+        assert!(complete::<Schur<3>>(13, &[], SymmetryBreaking::default()).is_some());
+        assert!(complete::<Schur<3>>(14, &[], SymmetryBreaking::default()).is_none());
+        assert!(complete::<Schur<3>>(13, &[], BROKEN).is_some());
+        assert!(complete::<Schur<3>>(14, &[], BROKEN).is_none());
+    }
+
+    #[test]
+    fn weak_schur_number() {
+        assert!(complete::<WeakSchur<3>>(23, &[], BROKEN).is_some());
+        assert!(complete::<WeakSchur<3>>(24, &[], BROKEN).is_none());
+    }
+
+    #[test]
+    fn prefix() {
+        let prefix = [0, 1, 1, 0];
+        let coloring = complete::<Schur<3>>(13, &prefix, SymmetryBreaking::default()).unwrap();
+        assert_eq!(coloring[..prefix.len()], prefix);
+
+        // Whether these prefixes can be completed is left to the DFS.
+        for prefix in [[0, 1, 0], [0, 1, 2]] {
+            let expected = Dfs::default().search::<Schur<3>>(13, &prefix).coloring;
+            let coloring = complete::<Schur<3>>(13, &prefix, SymmetryBreaking::default());
+            assert_eq!(coloring.is_some(), expected.is_some());
+            if let Some(coloring) = coloring {
+                assert_eq!(coloring[..prefix.len()], prefix);
+            }
+        }
+    }
+
+    /// The SAT solver finds a coloring exactly when the DFS counts one.
+    #[test]
+    fn dfs_count() {
+        for symmetry in [SymmetryBreaking::default(), BROKEN] {
+            let dfs = Dfs {
+                symmetry,
+                max_nodes: None,
+            };
+
+            for n in 1..=4 {
+                for prefix in [&[][..], &[0], &[0, 1], &[1, 0]] {
+                    if prefix.len() > n || !symmetry.allows_all::<Schur<2>>(prefix) {
+                        continue;
+                    }
+                    let solutions = dfs.count::<Schur<2>>(n, prefix).solutions;
+                    let coloring = complete::<Schur<2>>(n, prefix, symmetry);
+                    assert_eq!(coloring.is_some(), solutions > 0, "{n} {prefix:?}");
+                }
+            }
+
+            for n in 1..=13 {
+                for prefix in [&[][..], &[0], &[0, 1], &[0, 1, 1], &[0, 1, 0]] {
+                    if prefix.len() > n || !symmetry.allows_all::<Schur<3>>(prefix) {
+                        continue;
+                    }
+                    let solutions = dfs.count::<Schur<3>>(n, prefix).solutions;
+                    let coloring = complete::<Schur<3>>(n, prefix, symmetry);
+                    assert_eq!(coloring.is_some(), solutions > 0, "{n} {prefix:?}");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+pub mod beam;
+pub mod dfs;
+pub mod local;
+pub mod mcts;
+pub mod nmcs;
+
+use crate::{Coloring, Policy, SequenceColoring, SequenceProblem, assert_const_generics::*};
+use rand::RngCore;
+
+/// Estimate of the final size reachable from a coloring, over the upper bound.
+pub trait Evaluator<P>
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    fn value(&self, coloring: &SequenceColoring<P>, rng: &mut dyn RngCore) -> f64;
+
+    /// Values of a batch of colorings, for the evaluators evaluating states together.
+    fn values(&self, colorings: &[SequenceColoring<P>], rng: &mut dyn RngCore) -> Vec<f64> {
+        colorings
+            .iter()
+            .map(|coloring| self.value(coloring, rng))
+            .collect()
+    }
+}
+
+fn final_value<P>(coloring: &SequenceColoring<P>) -> f64
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    coloring.size() as f64 / (P::BOUND - 1) as f64
+}
+
+/// Colors still possible for the uncolored numbers, as a final size between the current size and
+/// the upper bound, on the scale of the final sizes of the terminal colorings.
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash, Ord, PartialOrd, Default)]
+pub struct PossibleCapacity;
+
+impl<P> Evaluator<P> for PossibleCapacity
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    fn value(&self, coloring: &SequenceColoring<P>, _rng: &mut dyn RngCore) -> f64 {
+        // The number of the upper bound is never possible.
+        let capacity = (0..P::N_COLORS)
+            .flat_map(|color| &coloring.possible()[color][coloring.size()..P::BOUND - 1])
+            .filter(|&&possible| possible)
+            .count();
+
+        let size = coloring.size() as f64 + capacity as f64 / P::N_COLORS as f64;
+        size / (P::BOUND - 1) as f64
+    }
+}
+
+/// Mean final size of games played by a policy.
+#[derive(Debug, Copy, Clone)]
+pub struct Rollout<'a, Pol: ?Sized> {
+    pub policy: &'a Pol,
+    pub n_rollouts: usize,
+}
+
+impl<P, Pol> Evaluator<P> for Rollout<'_, Pol>
+where
+    P: SequenceProblem,
+    Pol: Policy<P> + ?Sized,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    fn value(&self, coloring: &SequenceColoring<P>, mut rng: &mut dyn RngCore) -> f64 {
+        let total: f64 = (0..self.n_rollouts)
+            .map(|_| {
+                let mut coloring = coloring.clone();
+                while let Some(color) = self.policy.choose(&coloring, &mut rng) {
+                    coloring.play(color).expect("Illegal move.");
+                }
+
+                final_value(&coloring)
+            })
+            .sum();
+
+        total / self.n_rollouts as f64
+    }
+}
+
+/// Game played by a search, the expert targets of expert iteration.
+#[derive(Debug, Clone)]
+pub struct SearchGame<const N_COLORS: usize> {
+    pub coloring: Coloring<N_COLORS>,
+    /// Distribution of the colors chosen by the search at each size of the coloring.
+    pub targets: Vec<Vec<f64>>,
+}
+
+impl<const N_COLORS: usize> SearchGame<N_COLORS> {
+    fn new<P>(coloring: SequenceColoring<P>, targets: Vec<Vec<f64>>) -> Self
+    where
+        P: SequenceProblem,
+        Assert<{ N_COLORS == P::N_COLORS }>: IsTrue,
+        [(); P::BOUND]:,
+        [(); P::N_COLORS]:,
+    {
+        Self {
+            coloring: Coloring::from(coloring),
+            targets,
+        }
+    }
+}
+
+/// Checks that the targets of a game are distributions over the legal moves of its prefixes,
+/// giving mass to the moves played.
+#[cfg(test)]
+fn check_targets<P>(game: &SearchGame<{ P::N_COLORS }>)
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    assert_eq!(game.targets.len(), game.coloring.len());
+
+    let mut coloring = SequenceColoring::<P>::new();
+    for (&color, target) in game.coloring.iter().zip(&game.targets) {
+        assert_eq!(target.len(), P::N_COLORS);
+        assert!((target.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+        let moves = coloring.legal_moves();
+        for (other, &probability) in target.iter().enumerate() {
+            assert!(probability >= 0.0);
+            assert!(moves.contains(&other) || probability == 0.0);
+        }
+        assert!(target[color] > 0.0);
+
+        coloring.play(color).unwrap();
+    }
+    assert!(coloring.legal_moves().is_empty());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problems::Schur;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn possible_capacity() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut coloring = SequenceColoring::<Schur<3>>::new();
+        assert_eq!(PossibleCapacity.value(&coloring, &mut rng), 1.0);
+
+        while let Some(color) = coloring.random_move(&mut rng) {
+            coloring.play(color).unwrap();
+
+            let value = PossibleCapacity.value(&coloring, &mut rng);
+            assert!((final_value(&coloring)..=1.0).contains(&value));
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! Beam search keeping the best states of each size, scored by an evaluator.
+
+use super::Evaluator;
+use crate::{Coloring, SequenceColoring, SequenceProblem, assert_const_generics::*};
+use rand::RngCore;
+use std::collections::HashSet;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DepthStats {
+    /// Size of the colorings of the beam.
+    pub depth: usize,
+    /// Children of the previous beam.
+    pub candidates: usize,
+    /// Children equal to another one up to a permutation of the colors.
+    pub duplicates: usize,
+    pub kept: usize,
+    pub best_value: f64,
+    pub mean_value: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct BeamResult<const N_COLORS: usize> {
+    pub best: Coloring<N_COLORS>,
+    pub stats: Vec<DepthStats>,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct BeamSearch<'a, E: ?Sized> {
+    pub evaluator: &'a E,
+    pub width: usize,
+}
+
+impl<E: ?Sized> BeamSearch<'_, E> {
+    // Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+    #[allow(clippy::eq_op)]
+    pub fn search<P>(&self, rng: &mut dyn RngCore) -> BeamResult<{ P::N_COLORS }>
+    where
+        P: SequenceProblem,
+        E: Evaluator<P>,
+        Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+        [(); P::BOUND]:,
+        [(); P::N_COLORS]:,
+    {
+        let mut beam = vec![SequenceColoring::<P>::new()];
+        let mut stats = Vec::new();
+
+        loop {
+            let children: Vec<_> = beam
+                .iter()
+                .flat_map(|coloring| {
+                    coloring.legal_moves().into_iter().map(|color| {
+                        let mut child = coloring.clone();
+                        child.play(color).expect("Illegal move.");
+                        child
+                    })
+                })
+                .collect();
+            if children.is_empty() {
+                break;
+            }
+
+            let candidates = children.len();
+            let mut seen = HashSet::new();
+            let children: Vec<_> = children
+                .into_iter()
+                .filter(|child| {
+                    // Equal up to a permutation of the colors means the same continuations.
+                    let mut canonical = Coloring::<{ P::N_COLORS }>::from(child.clone());
+                    canonical.order_colors();
+                    seen.insert(canonical)
+                })
+                .collect();
+            let duplicates = candidates - children.len();
+
+            let values = self.evaluator.values(&children, rng);
+            let mut scored: Vec<_> = values.into_iter().zip(children).collect();
+            // Stable, so that the ties keep the order of the moves.
+            scored.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+            scored.truncate(self.width.max(1));
+
+            stats.push(DepthStats {
+                depth: beam[0].size() + 1,
+                candidates,
+                duplicates,
+                kept: scored.len(),
+                best_value: scored[0].0,
+                mean_value: scored.iter().map(|(value, _)| value).sum::<f64>()
+                    / scored.len() as f64,
+            });
+            beam = scored.into_iter().map(|(_, child)| child).collect();
+        }
+
+        // Every coloring of the last beam has the maximum size.
+        BeamResult {
+            best: Coloring::from(beam.swap_remove(0)),
+            stats,
+        }
+    }
+}
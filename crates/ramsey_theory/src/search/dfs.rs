@@ -0,0 +1,150 @@
+//! Exhaustive depth-first search of the colorings of `1..=n`, pruned by symmetry breaking.
+
+use crate::{
+    Coloring, SequenceColoring, SequenceProblem, assert_const_generics::*,
+    problems::ForbiddenSolutions, symmetry::SymmetryBreaking,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Dfs {
+    pub symmetry: SymmetryBreaking,
+    /// Stop after visiting this many colorings.
+    pub max_nodes: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DfsResult<const N_COLORS: usize> {
+    /// The first coloring of the size found.
+    pub coloring: Option<Coloring<N_COLORS>>,
+    /// Colorings of the size found.
+    pub solutions: usize,
+    /// Colorings visited, including the prefix.
+    pub nodes: usize,
+    /// Whether the search stopped at the maximum number of nodes.
+    pub stopped: bool,
+}
+
+struct Visit<'a> {
+    size: usize,
+    exhaustive: bool,
+    colors: &'a mut Vec<usize>,
+    first: Option<Vec<usize>>,
+    solutions: usize,
+    nodes: usize,
+}
+
+enum Outcome {
+    Found,
+    Exhausted,
+    Stopped,
+}
+
+impl Dfs {
+    fn visit<P>(&self, coloring: &SequenceColoring<P>, visit: &mut Visit) -> Outcome
+    where
+        P: SequenceProblem + ForbiddenSolutions,
+        [(); P::BOUND]:,
+        [(); P::N_COLORS]:,
+    {
+        visit.nodes += 1;
+        if visit.colors.len() == visit.size {
+            visit.solutions += 1;
+            visit.first.get_or_insert_with(|| visit.colors.clone());
+
+            return match visit.exhaustive {
+                true => Outcome::Exhausted,
+                false => Outcome::Found,
+            };
+        }
+        if self
+            .max_nodes
+            .is_some_and(|max_nodes| visit.nodes >= max_nodes)
+        {
+            return Outcome::Stopped;
+        }
+
+        for color in coloring.legal_moves() {
+            if !self.symmetry.allows::<P>(visit.colors, color) {
+                continue;
+            }
+
+            let mut child = coloring.clone();
+            child.play(color).expect("Illegal move.");
+            visit.colors.push(color);
+
+            let outcome = self.visit(&child, visit);
+            visit.colors.pop();
+            if !matches!(outcome, Outcome::Exhausted) {
+                return outcome;
+            }
+        }
+
+        Outcome::Exhausted
+    }
+
+    // Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+    #[allow(clippy::eq_op)]
+    fn run<P>(&self, size: usize, prefix: &[usize], exhaustive: bool) -> DfsResult<{ P::N_COLORS }>
+    where
+        P: SequenceProblem + ForbiddenSolutions,
+        Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+        [(); P::BOUND]:,
+        [(); P::N_COLORS]:,
+    {
+        assert!(
+            size < P::BOUND,
+            "No coloring is larger than the upper bound."
+        );
+        assert!(prefix.len() <= size, "The prefix is larger than the size.");
+
+        let mut coloring = SequenceColoring::<P>::new();
+        for &color in prefix {
+            coloring.play(color).expect("Illegal move.");
+        }
+
+        let mut colors = prefix.to_vec();
+        let mut visit = Visit {
+            size,
+            exhaustive,
+            colors: &mut colors,
+            first: None,
+            solutions: 0,
+            nodes: 0,
+        };
+        let outcome = self.visit(&coloring, &mut visit);
+
+        DfsResult {
+            coloring: visit.first.map(|colors| colors.into_iter().collect()),
+            solutions: visit.solutions,
+            nodes: visit.nodes,
+            stopped: matches!(outcome, Outcome::Stopped),
+        }
+    }
+
+    /// Searches a coloring of `1..=size` starting with `prefix`, which must be legal and allowed
+    /// by the symmetry breaking.
+    // Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+    #[allow(clippy::eq_op)]
+    pub fn search<P>(&self, size: usize, prefix: &[usize]) -> DfsResult<{ P::N_COLORS }>
+    where
+        P: SequenceProblem + ForbiddenSolutions,
+        Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+        [(); P::BOUND]:,
+        [(); P::N_COLORS]:,
+    {
+        self.run::<P>(size, prefix, false)
+    }
+
+    /// Visits every coloring of `1..=size` starting with `prefix`, like `search`.
+    // Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+    #[allow(clippy::eq_op)]
+    pub fn count<P>(&self, size: usize, prefix: &[usize]) -> DfsResult<{ P::N_COLORS }>
+    where
+        P: SequenceProblem + ForbiddenSolutions,
+        Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+        [(); P::BOUND]:,
+        [(); P::N_COLORS]:,
+    {
+        self.run::<P>(size, prefix, true)
+    }
+}
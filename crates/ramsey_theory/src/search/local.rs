@@ -0,0 +1,307 @@
+//! Simulated annealing with a tabu list on full colorings of `1..=n`, minimizing the number of
+//! monochromatic forbidden solutions.
+
+use crate::{
+    Coloring, SequenceColoring, SequenceProblem, assert_const_generics::*,
+    problems::ForbiddenSolutions,
+};
+use rand::{Rng, RngCore, seq::SliceRandom};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LocalSearch {
+    /// Recolorings tried for each size.
+    pub max_steps: usize,
+    /// Initial temperature of the annealing, 0 only accepts the moves not increasing the energy.
+    pub temperature: f64,
+    /// Factor of the temperature after each step.
+    pub cooling: f64,
+    /// Steps during which a number cannot get back its previous color.
+    pub tabu_tenure: usize,
+}
+
+impl Default for LocalSearch {
+    fn default() -> Self {
+        Self {
+            max_steps: 1_000_000,
+            temperature: 1.0,
+            cooling: 0.99999,
+            tabu_tenure: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LocalSearchResult<const N_COLORS: usize> {
+    pub size: usize,
+    pub steps: usize,
+    pub best_energy: usize,
+    /// The coloring of energy 0, if found.
+    pub coloring: Option<Coloring<N_COLORS>>,
+}
+
+/// Full coloring with, for each number and color, the solutions whose other numbers have the color.
+struct State {
+    n_colors: usize,
+    colors: Vec<usize>,
+    conflicts: Vec<usize>,
+    energy: usize,
+}
+
+impl State {
+    fn new<P>(colors: Vec<usize>, n_colors: usize) -> Self
+    where
+        P: ForbiddenSolutions,
+    {
+        let n = colors.len();
+        let mut conflicts = vec![0; n * n_colors];
+
+        for x in 1..=n {
+            P::for_each_solution(x, n, |others| {
+                let color = colors[others[0] - 1];
+                if others.iter().all(|&y| colors[y - 1] == color) {
+                    conflicts[(x - 1) * n_colors + color] += 1;
+                }
+            });
+        }
+
+        Self {
+            n_colors,
+            energy: P::monochromatic_count(&colors),
+            colors,
+            conflicts,
+        }
+    }
+
+    fn conflicts(&self, x: usize, color: usize) -> usize {
+        self.conflicts[(x - 1) * self.n_colors + color]
+    }
+
+    fn delta(&self, x: usize, color: usize) -> isize {
+        self.conflicts(x, color) as isize - self.conflicts(x, self.colors[x - 1]) as isize
+    }
+
+    fn recolor<P>(&mut self, x: usize, color: usize)
+    where
+        P: ForbiddenSolutions,
+    {
+        let previous = self.colors[x - 1];
+        self.energy = self.energy.wrapping_add_signed(self.delta(x, color));
+
+        let n_colors = self.n_colors;
+        let colors = &self.colors;
+        let conflicts = &mut self.conflicts;
+        P::for_each_solution(x, colors.len(), |others| {
+            for (index, &y) in others.iter().enumerate() {
+                // The solution counts for `y` when the numbers other than `x` and `y` have the
+                // color of `x`.
+                let mut rest = others
+                    .iter()
+                    .enumerate()
+                    .filter(|&(other, _)| other != index)
+                    .map(|(_, &z)| colors[z - 1]);
+                let rest_color = rest.next();
+                if !rest.all(|other_color| Some(other_color) == rest_color) {
+                    continue;
+                }
+
+                if rest_color.is_none_or(|rest_color| rest_color == previous) {
+                    conflicts[(y - 1) * n_colors + previous] -= 1;
+                }
+                if rest_color.is_none_or(|rest_color| rest_color == color) {
+                    conflicts[(y - 1) * n_colors + color] += 1;
+                }
+            }
+        });
+
+        self.colors[x - 1] = color;
+    }
+}
+
+impl LocalSearch {
+    /// Searches a coloring of `1..=colors.len()` starting from `colors`.
+    // Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+    #[allow(clippy::eq_op)]
+    pub fn solve<P>(
+        &self,
+        colors: Vec<usize>,
+        rng: &mut dyn RngCore,
+    ) -> LocalSearchResult<{ P::N_COLORS }>
+    where
+        P: SequenceProblem + ForbiddenSolutions,
+        Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+        [(); P::BOUND]:,
+        [(); P::N_COLORS]:,
+    {
+        let size = colors.len();
+        let mut state = State::new::<P>(colors, P::N_COLORS);
+        let mut tabu_until = vec![0; size * P::N_COLORS];
+        let mut temperature = self.temperature;
+        let mut best_energy = state.energy;
+        let mut best_colors = state.colors.clone();
+        let mut steps = 0;
+
+        while state.energy > 0 && steps < self.max_steps {
+            steps += 1;
+
+            let conflicting: Vec<_> = (1..=size)
+                .filter(|&x| state.conflicts(x, state.colors[x - 1]) > 0)
+                .collect();
+            let &x = conflicting.choose(rng).unwrap();
+
+            // The tabu moves are allowed when they lead to a new best energy.
+            let candidates: Vec<_> = (0..P::N_COLORS)
+                .filter(|&color| color != state.colors[x - 1])
+                .map(|color| (color, state.delta(x, color)))
+                .filter(|&(color, delta)| {
+                    tabu_until[(x - 1) * P::N_COLORS + color] <= steps
+                        || state.energy.wrapping_add_signed(delta) < best_energy
+                })
+                .collect();
+            let Some(min_delta) = candidates.iter().map(|&(_, delta)| delta).min() else {
+                continue;
+            };
+            let best_moves: Vec<_> = candidates
+                .into_iter()
+                .filter(|&(_, delta)| delta == min_delta)
+                .collect();
+            let &(color, delta) = best_moves.choose(rng).unwrap();
+
+            let accepted = delta <= 0
+                || temperature > 0.0 && rng.gen_bool((-delta as f64 / temperature).exp());
+            temperature *= self.cooling;
+            if !accepted {
+                continue;
+            }
+
+            tabu_until[(x - 1) * P::N_COLORS + state.colors[x - 1]] = steps + self.tabu_tenure + 1;
+            state.recolor::<P>(x, color);
+
+            if state.energy < best_energy {
+                best_energy = state.energy;
+                best_colors.clone_from(&state.colors);
+            }
+        }
+
+        let coloring = (best_energy == 0).then(|| {
+            // The incremental energy is checked against a count from scratch.
+            assert_eq!(P::monochromatic_count(&best_colors), 0);
+            // Beyond the upper bound, the game cannot replay the coloring.
+            if size < P::BOUND {
+                let mut coloring = SequenceColoring::<P>::new();
+                for (position, &color) in best_colors.iter().enumerate() {
+                    if let Err(err) = coloring.play(color) {
+                        panic!("The valid coloring is rejected at position {position}: {err}.");
+                    }
+                }
+            }
+            best_colors.into_iter().collect()
+        });
+
+        LocalSearchResult {
+            size,
+            steps,
+            best_energy,
+            coloring,
+        }
+    }
+
+    /// Solves `1..=start`, then each next size from the last solution extended by the color of
+    /// the new number with the fewest conflicts, until a size is not solved or `max_size`.
+    ///
+    /// Returns the largest coloring found, `report` gets the result of each size.
+    // Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+    #[allow(clippy::eq_op)]
+    pub fn increasing<P>(
+        &self,
+        start: usize,
+        max_size: usize,
+        rng: &mut dyn RngCore,
+        mut report: impl FnMut(&LocalSearchResult<{ P::N_COLORS }>),
+    ) -> Option<Coloring<{ P::N_COLORS }>>
+    where
+        P: SequenceProblem + ForbiddenSolutions,
+        Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+        [(); P::BOUND]:,
+        [(); P::N_COLORS]:,
+    {
+        let mut colors: Vec<_> = (0..start).map(|_| rng.gen_range(0..P::N_COLORS)).collect();
+        let mut best = None;
+
+        loop {
+            let result = self.solve::<P>(colors, rng);
+            report(&result);
+
+            let Some(coloring) = result.coloring else {
+                return best;
+            };
+            if coloring.len() >= max_size {
+                return Some(coloring);
+            }
+
+            colors = coloring.to_vec();
+            let x = colors.len() + 1;
+            let mut conflicts = [0; P::N_COLORS];
+            P::for_each_solution(x, x, |others| {
+                let color = colors[others[0] - 1];
+                if others.iter().all(|&y| colors[y - 1] == color) {
+                    conflicts[color] += 1;
+                }
+            });
+            let color = (0..P::N_COLORS)
+                .min_by_key(|&color| conflicts[color])
+                .unwrap();
+            colors.push(color);
+
+            best = Some(coloring);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problems::{Schur, WeakSchur};
+    use rand::{SeedableRng, rngs::StdRng};
+
+    /// Recolors random numbers, checking the incremental energy and conflicts against a state
+    /// built from scratch.
+    fn check_recolor<P>(size: usize, rng: &mut StdRng)
+    where
+        P: SequenceProblem + ForbiddenSolutions,
+    {
+        let colors = (0..size).map(|_| rng.gen_range(0..P::N_COLORS)).collect();
+        let mut state = State::new::<P>(colors, P::N_COLORS);
+
+        for _ in 0..200 {
+            let x = rng.gen_range(1..=size);
+            let color = rng.gen_range(0..P::N_COLORS);
+            let energy = state.energy as isize + state.delta(x, color);
+            state.recolor::<P>(x, color);
+
+            let expected = State::new::<P>(state.colors.clone(), P::N_COLORS);
+            assert_eq!(state.energy as isize, energy);
+            assert_eq!(state.energy, P::monochromatic_count(&state.colors));
+            assert_eq!(state.conflicts, expected.conflicts);
+        }
+    }
+
+    #[test]
+    fn recolor() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        check_recolor::<Schur<2>>(4, &mut rng);
+        check_recolor::<Schur<3>>(13, &mut rng);
+        check_recolor::<Schur<3>>(20, &mut rng);
+        check_recolor::<WeakSchur<3>>(23, &mut rng);
+        check_recolor::<WeakSchur<4>>(40, &mut rng);
+    }
+
+    #[test]
+    fn solve() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let search = LocalSearch::default();
+
+        let best = search.increasing::<Schur<3>>(1, 13, &mut rng, |_: &LocalSearchResult<3>| {});
+        assert_eq!(best.unwrap().len(), 13);
+    }
+}
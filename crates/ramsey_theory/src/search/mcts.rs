@@ -0,0 +1,241 @@
+//! Monte Carlo tree search with the PUCT rule of AlphaZero, the priors given by a policy.
+
+use super::{Evaluator, SearchGame, final_value};
+use crate::{Policy, SequenceColoring, SequenceProblem, assert_const_generics::*};
+use rand::{
+    RngCore,
+    distributions::{Distribution, WeightedIndex},
+};
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MctsConfig {
+    /// Simulations before each move, besides the expansion of the root.
+    pub simulations: usize,
+    /// Weight of the priors against the values.
+    pub exploration: f64,
+    /// The first moves are sampled from the visit counts instead of being the most visited.
+    pub sampled_moves: usize,
+}
+
+impl Default for MctsConfig {
+    fn default() -> Self {
+        Self {
+            simulations: 100,
+            exploration: 1.5,
+            sampled_moves: 0,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Mcts<'a, Pol: ?Sized, E: ?Sized> {
+    pub policy: &'a Pol,
+    pub evaluator: &'a E,
+    pub config: MctsConfig,
+}
+
+struct Node {
+    prior: f64,
+    visits: u32,
+    total_value: f64,
+    expanded: bool,
+    /// Colors and indices of the children.
+    children: Vec<(usize, usize)>,
+}
+
+impl Node {
+    fn new(prior: f64) -> Self {
+        Self {
+            prior,
+            visits: 0,
+            total_value: 0.0,
+            expanded: false,
+            children: Vec::new(),
+        }
+    }
+}
+
+struct Tree {
+    nodes: Vec<Node>,
+    // The values are close to each other, they are rescaled to the ones seen in the tree.
+    min_value: f64,
+    max_value: f64,
+}
+
+impl Tree {
+    fn new() -> Self {
+        Self {
+            nodes: vec![Node::new(1.0)],
+            min_value: f64::INFINITY,
+            max_value: f64::NEG_INFINITY,
+        }
+    }
+
+    fn normalized_value(&self, node: usize) -> f64 {
+        let node = &self.nodes[node];
+        let value = node.total_value / f64::from(node.visits);
+
+        if self.max_value > self.min_value {
+            (value - self.min_value) / (self.max_value - self.min_value)
+        } else {
+            0.5
+        }
+    }
+
+    fn select(&self, node: usize, exploration: f64) -> (usize, usize) {
+        let sqrt_visits = f64::from(self.nodes[node].visits).sqrt();
+        let parent_value = self.normalized_value(node);
+
+        let score = |child: usize| {
+            let child_node = &self.nodes[child];
+            let value = if child_node.visits == 0 {
+                parent_value
+            } else {
+                self.normalized_value(child)
+            };
+
+            value + exploration * child_node.prior * sqrt_visits / f64::from(1 + child_node.visits)
+        };
+
+        self.nodes[node]
+            .children
+            .iter()
+            .copied()
+            .max_by(|&(_, a), &(_, b)| score(a).total_cmp(&score(b)))
+            .unwrap()
+    }
+}
+
+impl<Pol: ?Sized, E: ?Sized> Mcts<'_, Pol, E> {
+    fn simulate<P>(&self, tree: &mut Tree, root: &SequenceColoring<P>, rng: &mut dyn RngCore)
+    where
+        P: SequenceProblem,
+        Pol: Policy<P>,
+        E: Evaluator<P>,
+        [(); P::BOUND]:,
+        [(); P::N_COLORS]:,
+    {
+        let mut coloring = root.clone();
+        let mut path = vec![0];
+        let mut node = 0;
+
+        while tree.nodes[node].expanded && !tree.nodes[node].children.is_empty() {
+            let (color, child) = tree.select(node, self.config.exploration);
+            coloring.play(color).expect("Illegal move.");
+            path.push(child);
+            node = child;
+        }
+
+        let moves = coloring.legal_moves();
+        let value = if moves.is_empty() {
+            final_value(&coloring)
+        } else {
+            let priors = self.policy.distribution(&coloring);
+            for color in moves {
+                let child = tree.nodes.len();
+                tree.nodes[node].children.push((color, child));
+                tree.nodes.push(Node::new(priors[color]));
+            }
+
+            self.evaluator.value(&coloring, rng)
+        };
+        tree.nodes[node].expanded = true;
+
+        for node in path {
+            tree.nodes[node].visits += 1;
+            tree.nodes[node].total_value += value;
+        }
+        tree.min_value = tree.min_value.min(value);
+        tree.max_value = tree.max_value.max(value);
+    }
+
+    // Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+    #[allow(clippy::eq_op)]
+    pub fn play<P>(&self, rng: &mut dyn RngCore) -> SearchGame<{ P::N_COLORS }>
+    where
+        P: SequenceProblem,
+        Pol: Policy<P>,
+        E: Evaluator<P>,
+        Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+        [(); P::BOUND]:,
+        [(); P::N_COLORS]:,
+    {
+        let mut coloring = SequenceColoring::<P>::new();
+        let mut targets = Vec::new();
+
+        while !coloring.legal_moves().is_empty() {
+            let mut tree = Tree::new();
+            // At least one simulation goes past the root to have a visited move.
+            for _ in 0..=self.config.simulations.max(1) {
+                self.simulate(&mut tree, &coloring, rng);
+            }
+
+            let mut visits = vec![0.0; P::N_COLORS];
+            for &(color, child) in &tree.nodes[0].children {
+                visits[color] = f64::from(tree.nodes[child].visits);
+            }
+            let total_visits: f64 = visits.iter().sum();
+
+            let color = if targets.len() < self.config.sampled_moves {
+                WeightedIndex::new(&visits).unwrap().sample(rng)
+            } else {
+                (0..P::N_COLORS)
+                    .max_by(|&a, &b| visits[a].total_cmp(&visits[b]))
+                    .unwrap()
+            };
+            targets.push(visits.iter().map(|visits| visits / total_visits).collect());
+
+            coloring.play(color).expect("Illegal move.");
+        }
+
+        SearchGame::new(coloring, targets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        policies::UniformRandom,
+        problems::{Schur, WeakSchur},
+        search::{PossibleCapacity, check_targets},
+    };
+    use rand::{SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn targets_are_distributions_over_the_legal_moves() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mcts = Mcts {
+            policy: &UniformRandom,
+            evaluator: &PossibleCapacity,
+            config: MctsConfig {
+                simulations: 50,
+                sampled_moves: 2,
+                ..Default::default()
+            },
+        };
+
+        check_targets::<Schur<2>>(&mcts.play::<Schur<2>>(&mut rng));
+        check_targets::<Schur<3>>(&mcts.play::<Schur<3>>(&mut rng));
+        check_targets::<WeakSchur<3>>(&mcts.play::<WeakSchur<3>>(&mut rng));
+    }
+
+    #[test]
+    fn most_visited_moves() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mcts = Mcts {
+            policy: &UniformRandom,
+            evaluator: &PossibleCapacity,
+            config: MctsConfig::default(),
+        };
+
+        let game = mcts.play::<Schur<3>>(&mut rng);
+        for (&color, target) in game.coloring.iter().zip(&game.targets) {
+            assert!(
+                target
+                    .iter()
+                    .all(|&probability| probability <= target[color])
+            );
+        }
+    }
+}
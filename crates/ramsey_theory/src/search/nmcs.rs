@@ -0,0 +1,152 @@
+//! Nested Monte Carlo search, the playouts of level 0 follow a policy.
+
+use super::SearchGame;
+use crate::{Policy, SequenceColoring, SequenceProblem, assert_const_generics::*};
+use rand::RngCore;
+
+fn one_hot<P>(color: usize) -> Vec<f64>
+where
+    P: SequenceProblem,
+{
+    let mut target = vec![0.0; P::N_COLORS];
+    target[color] = 1.0;
+
+    target
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Nmcs<'a, Pol: ?Sized> {
+    pub policy: &'a Pol,
+    pub level: usize,
+}
+
+impl<Pol: ?Sized> Nmcs<'_, Pol> {
+    /// Best game of the searches of the lower level after each move, followed from `coloring`.
+    fn search<P>(
+        &self,
+        mut coloring: SequenceColoring<P>,
+        level: usize,
+        rng: &mut dyn RngCore,
+        mut targets: Option<&mut Vec<Vec<f64>>>,
+    ) -> SequenceColoring<P>
+    where
+        P: SequenceProblem,
+        Pol: Policy<P>,
+        [(); P::BOUND]:,
+        [(); P::N_COLORS]:,
+    {
+        if level == 0 {
+            while let Some(color) = self.policy.choose(&coloring, &mut *rng) {
+                if let Some(targets) = targets.as_deref_mut() {
+                    targets.push(one_hot::<P>(color));
+                }
+                coloring.play(color).expect("Illegal move.");
+            }
+
+            return coloring;
+        }
+
+        // The best game always extends the current coloring, so it can be followed.
+        let mut best: Option<Vec<usize>> = None;
+
+        loop {
+            let moves = coloring.legal_moves();
+            if moves.is_empty() {
+                return coloring;
+            }
+
+            for color in moves {
+                let mut next = coloring.clone();
+                next.play(color).expect("Illegal move.");
+
+                let game = self.search(next, level - 1, rng, None);
+                if best.as_ref().is_none_or(|best| game.size() > best.len()) {
+                    best = Some(game.into_iter().collect());
+                }
+            }
+
+            let color = best.as_ref().unwrap()[coloring.size()];
+            if let Some(targets) = targets.as_deref_mut() {
+                targets.push(one_hot::<P>(color));
+            }
+
+            coloring.play(color).expect("Illegal move.");
+        }
+    }
+
+    // Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+    #[allow(clippy::eq_op)]
+    pub fn play<P>(&self, rng: &mut dyn RngCore) -> SearchGame<{ P::N_COLORS }>
+    where
+        P: SequenceProblem,
+        Pol: Policy<P>,
+        Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+        [(); P::BOUND]:,
+        [(); P::N_COLORS]:,
+    {
+        let mut targets = Vec::new();
+        let coloring = self.search(
+            SequenceColoring::<P>::new(),
+            self.level,
+            rng,
+            Some(&mut targets),
+        );
+
+        SearchGame::new(coloring, targets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        policies::UniformRandom,
+        problems::{Schur, WeakSchur},
+        search::check_targets,
+    };
+    use rand::{SeedableRng, rngs::StdRng};
+
+    #[test]
+    fn targets_are_the_moves_played() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for level in 0..=2 {
+            let nmcs = Nmcs {
+                policy: &UniformRandom,
+                level,
+            };
+            let game = nmcs.play::<Schur<3>>(&mut rng);
+
+            check_targets::<Schur<3>>(&game);
+            for (&color, target) in game.coloring.iter().zip(&game.targets) {
+                assert_eq!(*target, one_hot::<Schur<3>>(color));
+            }
+
+            check_targets::<WeakSchur<3>>(&nmcs.play::<WeakSchur<3>>(&mut rng));
+        }
+    }
+
+    #[test]
+    fn level_1_reaches_the_optimum() {
+        let nmcs = Nmcs {
+            policy: &UniformRandom,
+            level: 1,
+        };
+
+        for seed in 0..10 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            assert_eq!(nmcs.play::<Schur<2>>(&mut rng).coloring.len(), 4);
+        }
+    }
+
+    #[test]
+    fn level_2_reaches_the_optimum() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let nmcs = Nmcs {
+            policy: &UniformRandom,
+            level: 2,
+        };
+
+        assert_eq!(nmcs.play::<Schur<3>>(&mut rng).coloring.len(), 13);
+    }
+}
@@ -0,0 +1,65 @@
+//! Symmetry breaking against the `N_COLORS!` permutations of the colors, shared by the DFS
+//! solver and the CNF encoding.
+
+use crate::{cnf::variable, problems::ForbiddenSolutions};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SymmetryBreaking {
+    /// Colors used in order of first appearance, as relabeled by `Coloring::order_colors`.
+    pub first_appearance: bool,
+    /// Colors of the first numbers given by `ForbiddenSolutions::canonical_start`.
+    pub fixed_start: bool,
+}
+
+impl SymmetryBreaking {
+    /// Whether the number after `colors` can have `color`.
+    pub fn allows<P>(&self, colors: &[usize], color: usize) -> bool
+    where
+        P: ForbiddenSolutions,
+    {
+        if self.fixed_start {
+            if let Some(&fixed) = P::canonical_start().get(colors.len()) {
+                return color == fixed;
+            }
+        }
+
+        // The colors used so far are `0..n_used`.
+        let n_used = colors.iter().max().map_or(0, |&max| max + 1);
+        !self.first_appearance || color <= n_used
+    }
+
+    /// Whether no color of `colors` is ruled out.
+    pub fn allows_all<P>(&self, colors: &[usize]) -> bool
+    where
+        P: ForbiddenSolutions,
+    {
+        (0..colors.len()).all(|size| self.allows::<P>(&colors[..size], colors[size]))
+    }
+
+    /// Clauses in the variables of `crate::cnf` ruling out the same colorings of `1..=n`.
+    pub fn clauses<P>(&self, n: usize, n_colors: usize) -> Vec<Vec<i32>>
+    where
+        P: ForbiddenSolutions,
+    {
+        let mut clauses = Vec::new();
+
+        if self.fixed_start {
+            for (index, &color) in P::canonical_start().iter().take(n).enumerate() {
+                clauses.push(vec![variable(index + 1, color, n_colors)]);
+            }
+        }
+
+        if self.first_appearance {
+            // `x` can have the color `c` if a number before `x` has the color `c - 1`.
+            for x in 1..=n {
+                for color in 1..n_colors {
+                    let mut clause = vec![-variable(x, color, n_colors)];
+                    clause.extend((1..x).map(|y| variable(y, color - 1, n_colors)));
+                    clauses.push(clause);
+                }
+            }
+        }
+
+        clauses
+    }
+}
@@ -0,0 +1,217 @@
+use crate::{PlayError, SequenceColoring, SequenceProblem, observation::observations};
+use ndarray::{Array1, Array2, Array3};
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct VecEnvConfig {
+    /// Reward of each legal move.
+    pub move_reward: f32,
+    /// Reward of an invalid or illegal action.
+    pub illegal_move_reward: f32,
+    /// Added to the last move reward when no legal move is left.
+    pub dead_end_reward: f32,
+    /// Added to the last move reward when the coloring reaches the upper bound.
+    pub full_reward: f32,
+    /// End the game on an invalid or illegal action, otherwise the action is ignored.
+    pub illegal_move_ends_game: bool,
+}
+
+impl Default for VecEnvConfig {
+    fn default() -> Self {
+        Self {
+            move_reward: 1.0,
+            illegal_move_reward: -1.0,
+            dead_end_reward: 0.0,
+            full_reward: 0.0,
+            illegal_move_ends_game: true,
+        }
+    }
+}
+
+pub struct Step {
+    /// Observations of the next states, after the finished games have been reset.
+    pub observations: Array3<f32>,
+    pub rewards: Array1<f32>,
+    pub dones: Array1<bool>,
+    pub action_masks: Array2<bool>,
+    /// Sizes of the colorings before the finished games have been reset.
+    pub sizes: Array1<u32>,
+    pub errors: Vec<Option<PlayError>>,
+}
+
+/// Games played in lockstep, a finished game is automatically reset.
+pub struct VecEnv<P>
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    states: Vec<SequenceColoring<P>>,
+    config: VecEnvConfig,
+}
+
+impl<P> VecEnv<P>
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    pub fn new(n_envs: usize, config: VecEnvConfig) -> Self {
+        Self {
+            states: vec![SequenceColoring::new(); n_envs],
+            config,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    pub fn states(&self) -> &[SequenceColoring<P>] {
+        &self.states
+    }
+
+    pub fn config(&self) -> &VecEnvConfig {
+        &self.config
+    }
+
+    pub fn observations(&self) -> Array3<f32> {
+        observations(&self.states)
+    }
+
+    pub fn action_masks(&self) -> Array2<bool> {
+        let mut masks = Array2::from_elem((self.len(), P::N_COLORS), false);
+
+        for (state, mut mask) in self.states.iter().zip(masks.rows_mut()) {
+            for color in state.legal_moves() {
+                mask[color] = true;
+            }
+        }
+
+        masks
+    }
+
+    /// Resets every game, returns the observations and the action masks.
+    pub fn reset(&mut self) -> (Array3<f32>, Array2<bool>) {
+        self.states.fill(SequenceColoring::new());
+
+        (self.observations(), self.action_masks())
+    }
+
+    pub fn step(&mut self, actions: &[usize]) -> Step {
+        assert_eq!(actions.len(), self.len(), "One action per game is needed.");
+
+        let config = self.config;
+        let mut rewards = Array1::zeros(self.len());
+        let mut dones = Array1::from_elem(self.len(), false);
+        let mut sizes = Array1::zeros(self.len());
+        let mut errors = vec![None; self.len()];
+
+        for (index, (state, &action)) in self.states.iter_mut().zip(actions).enumerate() {
+            let (reward, done) = match state.play(action) {
+                Ok(()) if state.is_full() => (config.move_reward + config.full_reward, true),
+                Ok(()) if state.legal_moves().is_empty() => {
+                    (config.move_reward + config.dead_end_reward, true)
+                }
+                Ok(()) => (config.move_reward, false),
+                Err(err) => {
+                    errors[index] = Some(err);
+                    (config.illegal_move_reward, config.illegal_move_ends_game)
+                }
+            };
+
+            rewards[index] = reward;
+            dones[index] = done;
+            sizes[index] = state.size() as u32;
+
+            if done {
+                *state = SequenceColoring::new();
+            }
+        }
+
+        Step {
+            observations: self.observations(),
+            rewards,
+            dones,
+            action_masks: self.action_masks(),
+            sizes,
+            errors,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::problems::Schur;
+
+    fn config() -> VecEnvConfig {
+        VecEnvConfig {
+            move_reward: 1.0,
+            illegal_move_reward: -1.0,
+            dead_end_reward: -10.0,
+            full_reward: 10.0,
+            illegal_move_ends_game: true,
+        }
+    }
+
+    #[test]
+    fn finished_games_are_reset() {
+        let mut env = VecEnv::<Schur<2>>::new(2, config());
+        let (observations, action_masks) = env.reset();
+        assert_eq!(observations.shape(), [2, 5, 4]);
+        assert!(action_masks.iter().all(|&legal| legal));
+
+        // 0, 1, 1, 0 reaches the upper bound 4 and 0, 1, 0 leaves no legal move for 4.
+        for actions in [[0, 0], [1, 1]] {
+            let step = env.step(&actions);
+            assert_eq!(step.rewards.to_vec(), [1.0, 1.0]);
+            assert_eq!(step.dones.to_vec(), [false, false]);
+        }
+
+        let step = env.step(&[1, 0]);
+        assert_eq!(step.rewards.to_vec(), [1.0, -9.0]);
+        assert_eq!(step.dones.to_vec(), [false, true]);
+        assert_eq!(step.sizes.to_vec(), [3, 3]);
+        assert_eq!(env.states()[1].size(), 0);
+        assert!(step.action_masks.row(1).iter().all(|&legal| legal));
+
+        let step = env.step(&[0, 0]);
+        assert_eq!(step.rewards.to_vec(), [11.0, 1.0]);
+        assert_eq!(step.dones.to_vec(), [true, false]);
+        assert_eq!(step.sizes.to_vec(), [4, 1]);
+        assert_eq!(env.states()[0].size(), 0);
+        assert_eq!(step.observations, env.observations());
+        assert!(step.errors.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn illegal_moves() {
+        let mut env = VecEnv::<Schur<2>>::new(2, config());
+
+        env.step(&[0, 0]);
+        // 2 cannot have the color of 1.
+        let step = env.step(&[0, 2]);
+        assert_eq!(step.errors, [
+            Some(PlayError::IllegalMove),
+            Some(PlayError::InvalidColor)
+        ]);
+        assert_eq!(step.rewards.to_vec(), [-1.0, -1.0]);
+        assert_eq!(step.dones.to_vec(), [true, true]);
+        assert!(env.states().iter().all(|state| state.size() == 0));
+
+        let mut env = VecEnv::<Schur<2>>::new(1, VecEnvConfig {
+            illegal_move_ends_game: false,
+            ..config()
+        });
+        env.step(&[0]);
+        let step = env.step(&[0]);
+        assert_eq!(step.errors, [Some(PlayError::IllegalMove)]);
+        assert_eq!(step.dones.to_vec(), [false]);
+        assert_eq!(step.sizes.to_vec(), [1]);
+        assert_eq!(step.action_masks.row(0).to_vec(), [false, true]);
+    }
+}
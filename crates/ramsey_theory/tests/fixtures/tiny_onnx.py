@@ -0,0 +1,94 @@
+"""Writes tiny.onnx, a hand-built ONNX model of Schur<3> without the onnx package.
+
+The logits are the numbers of possible numbers of each color, the sums of the
+possible planes of the observations, and the value is the sum of all the
+observation channels. tiny_policy.onnx is the same model without the value.
+"""
+
+import struct
+from pathlib import Path
+
+VARINT, BYTES = 0, 2
+FLOAT, INT64 = 1, 7
+ATTRIBUTE_INTS = 7
+N_COLORS, N_CHANNELS, LEN = 3, 7, 13
+
+
+def varint(value):
+    out = bytearray()
+    while True:
+        byte = value & 0x7F
+        value >>= 7
+        if value:
+            out.append(byte | 0x80)
+        else:
+            out.append(byte)
+            return bytes(out)
+
+
+def field(number, value):
+    if isinstance(value, int):
+        return varint(number << 3 | VARINT) + varint(value)
+    if isinstance(value, str):
+        value = value.encode()
+    return varint(number << 3 | BYTES) + varint(len(value)) + value
+
+
+def message(*fields):
+    return b"".join(field(number, value) for number, value in fields)
+
+
+def ints_attribute(name, values):
+    fields = [(1, name), (20, ATTRIBUTE_INTS)] + [(8, value) for value in values]
+    return message(*fields)
+
+
+def int_attribute(name, value):
+    return message((1, name), (20, 2), (3, value))
+
+
+def node(op_type, inputs, outputs, *attributes):
+    fields = [(1, name) for name in inputs] + [(2, name) for name in outputs]
+    fields += [(4, op_type)] + [(5, attribute) for attribute in attributes]
+    return message(*fields)
+
+
+def int64_tensor(name, values):
+    raw = struct.pack(f"<{len(values)}q", *values)
+    return message((1, len(values)), (2, INT64), (8, name), (9, raw))
+
+
+def value_info(name, dims):
+    shape = message(
+        *[(1, message((2, dim) if isinstance(dim, str) else (1, dim))) for dim in dims]
+    )
+    tensor_type = message((1, FLOAT), (2, shape))
+    return message((1, name), (2, message((1, tensor_type))))
+
+
+def model(with_values):
+    nodes = [
+        node("ReduceSum", ["observations"], ["sums"],
+             ints_attribute("axes", [2]), int_attribute("keepdims", 0)),
+        node("Slice", ["sums", "starts", "ends", "axes"], ["logits"]),
+    ]
+    outputs = [value_info("logits", ["batch", N_COLORS])]
+    if with_values:
+        nodes.append(node("ReduceSum", ["observations"], ["values"],
+                          ints_attribute("axes", [1, 2]), int_attribute("keepdims", 0)))
+        outputs.append(value_info("values", ["batch"]))
+
+    graph = message(
+        *[(1, node) for node in nodes],
+        (2, "tiny"),
+        (5, int64_tensor("starts", [N_COLORS])),
+        (5, int64_tensor("ends", [2 * N_COLORS])),
+        (5, int64_tensor("axes", [1])),
+        (11, value_info("observations", ["batch", N_CHANNELS, LEN])),
+        *[(12, output) for output in outputs],
+    )
+    return message((1, 7), (2, "tiny_onnx.py"), (7, graph), (8, message((1, ""), (2, 11))))
+
+
+Path(__file__).with_name("tiny.onnx").write_bytes(model(with_values=True))
+Path(__file__).with_name("tiny_policy.onnx").write_bytes(model(with_values=False))
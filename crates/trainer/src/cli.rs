@@ -0,0 +1,173 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use data_generator::Problem;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Train a policy-value network on datasets of `data_generator`
+    Train(TrainArgs),
+    /// Alternate games of a search guided by the model and trainings on these games
+    Selfplay(SelfplayArgs),
+}
+
+#[derive(Args)]
+pub struct TrainArgs {
+    #[arg(short, long, value_enum)]
+    pub problem: Problem,
+
+    #[arg(short, long)]
+    pub colors: usize,
+
+    /// Datasets written by `data_generator`
+    #[arg(short, long, required = true, num_args = 1..)]
+    pub data: Vec<PathBuf>,
+
+    /// Directory of the configuration, the checkpoints and the trained model
+    #[arg(short, long)]
+    pub output_dir: PathBuf,
+
+    /// Continue from the checkpoint of the output directory
+    #[arg(long, conflicts_with = "init_model")]
+    pub resume: bool,
+
+    #[command(flatten)]
+    pub training: TrainingArgs,
+
+    #[command(flatten)]
+    pub net: NetArgs,
+}
+
+#[derive(Args)]
+pub struct SelfplayArgs {
+    #[arg(short, long, value_enum)]
+    pub problem: Problem,
+
+    #[arg(short, long)]
+    pub colors: usize,
+
+    /// Directory of the games, the models and the history of the iterations
+    #[arg(short, long)]
+    pub output_dir: PathBuf,
+
+    #[arg(short, long)]
+    #[arg(default_value_t = 10)]
+    pub iterations: usize,
+
+    /// Games played by the search at each iteration
+    #[arg(short, long)]
+    #[arg(default_value_t = 100)]
+    pub games: usize,
+
+    #[arg(long, value_enum)]
+    #[arg(default_value_t = SearchKind::Mcts)]
+    pub search: SearchKind,
+
+    /// Simulations of MCTS before each move
+    #[arg(long)]
+    #[arg(default_value_t = 100)]
+    pub simulations: usize,
+
+    /// Weight of the priors of MCTS against the values
+    #[arg(long)]
+    #[arg(default_value_t = 1.5)]
+    pub exploration: f64,
+
+    /// Number of first moves of MCTS sampled from the visit counts
+    #[arg(long)]
+    #[arg(default_value_t = 10)]
+    pub sampled_moves: usize,
+
+    /// Level of NMCS
+    #[arg(long)]
+    #[arg(default_value_t = 1)]
+    pub level: usize,
+
+    /// Number of latest iterations whose games are trained on
+    #[arg(long)]
+    #[arg(default_value_t = 4)]
+    pub window: usize,
+
+    #[arg(short, long)]
+    pub workers: Option<usize>,
+
+    #[command(flatten)]
+    pub training: TrainingArgs,
+
+    #[command(flatten)]
+    pub net: NetArgs,
+}
+
+#[derive(Args, Clone)]
+pub struct TrainingArgs {
+    /// Total number of epochs, including the ones before a resumed training
+    #[arg(short, long)]
+    #[arg(default_value_t = 10)]
+    pub epochs: usize,
+
+    #[arg(short, long)]
+    #[arg(default_value_t = 256)]
+    pub batch_size: usize,
+
+    #[arg(long)]
+    #[arg(default_value_t = 1e-3)]
+    pub learning_rate: f64,
+
+    /// Weight of the value loss added to the policy loss
+    #[arg(long)]
+    #[arg(default_value_t = 1.0)]
+    pub value_weight: f64,
+
+    /// Fraction of the samples held out to report the validation loss
+    #[arg(long)]
+    #[arg(default_value_t = 0.05)]
+    pub validation: f64,
+
+    /// Seed of the initialization and of the shuffling
+    #[arg(long)]
+    #[arg(default_value_t = 0)]
+    pub seed: u64,
+
+    /// Start from the weights of a model trained by `trainer`
+    #[arg(long)]
+    pub init_model: Option<PathBuf>,
+}
+
+#[derive(Args, Clone)]
+pub struct NetArgs {
+    /// Number of 1D convolutions before the dense layers, 0 for an MLP
+    #[arg(long)]
+    #[arg(default_value_t = 0)]
+    pub conv_layers: usize,
+
+    #[arg(long)]
+    #[arg(default_value_t = 32)]
+    pub conv_filters: usize,
+
+    /// Odd size of the convolution kernels
+    #[arg(long)]
+    #[arg(default_value_t = 5)]
+    pub kernel_size: usize,
+
+    #[arg(long)]
+    #[arg(default_value_t = 2)]
+    pub hidden_layers: usize,
+
+    #[arg(long)]
+    #[arg(default_value_t = 256)]
+    pub hidden_size: usize,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum SearchKind {
+    /// Monte Carlo tree search with the priors and the values of the model
+    Mcts,
+    /// Nested Monte Carlo search with playouts of the model
+    Nmcs,
+}
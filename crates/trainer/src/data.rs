@@ -0,0 +1,183 @@
+use ndarray::{Array1, Array2, Array3, Axis, concatenate};
+use ndarray_npy::NpzReader;
+use ramsey_theory::{
+    CompressedColoring, SequenceColoring, SequenceProblem,
+    observation::{observations, shape},
+};
+use std::{fs::File, path::Path};
+
+pub struct TrainingData {
+    pub observations: Array3<f32>,
+    /// One-hot legal moves, the target of the policy head.
+    pub legal_moves: Array2<f32>,
+    /// Final sizes of the games over the upper bound, when every dataset has them.
+    pub values: Option<Array1<f32>>,
+    /// Distributions of the moves of a search, which replace the legal moves as the
+    /// target of the policy head when every dataset has them.
+    pub policies: Option<Array2<f32>>,
+}
+
+impl TrainingData {
+    pub fn len(&self) -> usize {
+        self.legal_moves.nrows()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn select(&self, indices: &[usize]) -> Self {
+        Self {
+            observations: self.observations.select(Axis(0), indices),
+            legal_moves: self.legal_moves.select(Axis(0), indices),
+            values: self
+                .values
+                .as_ref()
+                .map(|values| values.select(Axis(0), indices)),
+            policies: self
+                .policies
+                .as_ref()
+                .map(|policies| policies.select(Axis(0), indices)),
+        }
+    }
+}
+
+fn colorings<P>(
+    compressed: &Array2<u32>,
+    sizes: &Array1<u32>,
+) -> Result<Vec<SequenceColoring<P>>, String>
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    compressed
+        .rows()
+        .into_iter()
+        .zip(sizes)
+        .enumerate()
+        .map(|(index, (row, &size))| {
+            let compressed =
+                CompressedColoring::<{ P::N_COLORS }>::from_compressed(row.to_vec(), size as usize);
+
+            let mut coloring = SequenceColoring::<P>::new();
+            for color in compressed.decompress() {
+                coloring
+                    .play(color)
+                    .map_err(|err| format!("the coloring {index} is invalid: {err}"))?;
+            }
+
+            Ok(coloring)
+        })
+        .collect()
+}
+
+fn load_dataset<P>(filename: &Path) -> Result<TrainingData, String>
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let error = |err: &dyn std::fmt::Display| format!("cannot read {}: {err}", filename.display());
+
+    let file = File::open(filename).map_err(|err| error(&err))?;
+    let mut npz = NpzReader::new(file).map_err(|err| error(&err))?;
+    let names = npz.names().map_err(|err| error(&err))?;
+
+    let legal_moves: Array2<bool> = npz.by_name("legal_moves.npy").map_err(|err| error(&err))?;
+    if legal_moves.ncols() != P::N_COLORS {
+        return Err(format!(
+            "{} has {} colors instead of {}",
+            filename.display(),
+            legal_moves.ncols(),
+            P::N_COLORS
+        ));
+    }
+
+    let observations = if names.iter().any(|name| name == "observations.npy") {
+        let observations: Array3<f32> =
+            npz.by_name("observations.npy").map_err(|err| error(&err))?;
+        let (n_channels, len) = shape::<P>();
+        if observations.shape()[1..] != [n_channels, len] {
+            return Err(format!(
+                "the observations of {} are not of this problem",
+                filename.display()
+            ));
+        }
+
+        observations
+    } else {
+        let compressed: Array2<u32> = npz.by_name("colorings.npy").map_err(|err| error(&err))?;
+        let sizes: Array1<u32> = npz.by_name("sizes.npy").map_err(|err| error(&err))?;
+        let colorings = colorings::<P>(&compressed, &sizes)
+            .map_err(|err| format!("{}: {err}", filename.display()))?;
+
+        observations(&colorings)
+    };
+
+    // The datasets written before the final sizes have no value labels.
+    let values = names
+        .iter()
+        .any(|name| name == "final_size.npy")
+        .then(|| -> Result<_, String> {
+            let final_sizes: Array1<u32> =
+                npz.by_name("final_size.npy").map_err(|err| error(&err))?;
+            Ok(final_sizes.mapv(|size| size as f32 / (P::BOUND - 1) as f32))
+        })
+        .transpose()?;
+
+    let policies = names
+        .iter()
+        .any(|name| name == "policy.npy")
+        .then(|| npz.by_name("policy.npy").map_err(|err| error(&err)))
+        .transpose()?;
+
+    Ok(TrainingData {
+        observations,
+        legal_moves: legal_moves.mapv(|legal| f32::from(u8::from(legal))),
+        values,
+        policies,
+    })
+}
+
+pub fn load_datasets<P>(filenames: &[impl AsRef<Path>]) -> Result<TrainingData, String>
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let datasets = filenames
+        .iter()
+        .map(|filename| load_dataset::<P>(filename.as_ref()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let observations: Vec<_> = datasets
+        .iter()
+        .map(|data| data.observations.view())
+        .collect();
+    let legal_moves: Vec<_> = datasets
+        .iter()
+        .map(|data| data.legal_moves.view())
+        .collect();
+    let values: Option<Vec<_>> = datasets
+        .iter()
+        .map(|data| data.values.as_ref().map(Array1::view))
+        .collect();
+    let policies: Option<Vec<_>> = datasets
+        .iter()
+        .map(|data| data.policies.as_ref().map(Array2::view))
+        .collect();
+
+    Ok(TrainingData {
+        observations: concatenate(Axis(0), &observations).map_err(|err| err.to_string())?,
+        legal_moves: concatenate(Axis(0), &legal_moves).map_err(|err| err.to_string())?,
+        values: values
+            .map(|values| concatenate(Axis(0), &values))
+            .transpose()
+            .map_err(|err| err.to_string())?,
+        policies: policies
+            .map(|policies| concatenate(Axis(0), &policies))
+            .transpose()
+            .map_err(|err| err.to_string())?,
+    })
+}
@@ -0,0 +1,17 @@
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+mod cli;
+mod data;
+mod model;
+mod policy;
+mod run;
+mod selfplay;
+mod train;
+
+pub use cli::{Cli, Command, NetArgs, SearchKind, SelfplayArgs, TrainArgs, TrainingArgs};
+pub use data::{TrainingData, load_datasets};
+pub use model::{Net, NetConfig};
+pub use policy::{Prediction, TrainedPolicy};
+pub use run::{get_selfplay_fn_pointer, get_train_fn_pointer};
+pub use train::{TrainingConfig, load_model};
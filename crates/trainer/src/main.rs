@@ -0,0 +1,27 @@
+use clap::Parser;
+use trainer::{Cli, Command, get_selfplay_fn_pointer, get_train_fn_pointer};
+
+fn main() {
+    #[cfg(debug_assertions)]
+    {
+        use std::env;
+
+        unsafe {
+            // SAFETY: Only the main thread is running
+            env::set_var("RUST_BACKTRACE", "1");
+        }
+    }
+
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Train(args) => {
+            let train = get_train_fn_pointer(args.problem, args.colors);
+            train(args);
+        }
+        Command::Selfplay(args) => {
+            let selfplay = get_selfplay_fn_pointer(args.problem, args.colors);
+            selfplay(args);
+        }
+    }
+}
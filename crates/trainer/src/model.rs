@@ -0,0 +1,107 @@
+use burn::{
+    config::Config,
+    module::Module,
+    nn::{
+        Linear, LinearConfig, PaddingConfig1d, Relu,
+        conv::{Conv1d, Conv1dConfig},
+    },
+    tensor::{Tensor, activation::sigmoid, backend::Backend},
+};
+
+/// Network of the observations of `ramsey_theory::observation`.
+#[derive(Config, Debug)]
+pub struct NetConfig {
+    pub n_channels: usize,
+    pub len: usize,
+    pub n_colors: usize,
+    /// Without convolutions, the network is an MLP.
+    #[config(default = 0)]
+    pub conv_layers: usize,
+    #[config(default = 32)]
+    pub conv_filters: usize,
+    #[config(default = 5)]
+    pub kernel_size: usize,
+    #[config(default = 2)]
+    pub hidden_layers: usize,
+    #[config(default = 256)]
+    pub hidden_size: usize,
+}
+
+#[derive(Module, Debug)]
+pub struct Net<B: Backend> {
+    convs: Vec<Conv1d<B>>,
+    hidden: Vec<Linear<B>>,
+    policy: Linear<B>,
+    value: Linear<B>,
+    activation: Relu,
+}
+
+impl NetConfig {
+    pub fn init<B: Backend>(&self, device: &B::Device) -> Net<B> {
+        let convs = (0..self.conv_layers)
+            .map(|layer| {
+                let channels_in = if layer == 0 {
+                    self.n_channels
+                } else {
+                    self.conv_filters
+                };
+
+                // The padding keeps the length so that the channels stay aligned with the numbers.
+                Conv1dConfig::new(channels_in, self.conv_filters, self.kernel_size)
+                    .with_padding(PaddingConfig1d::Same)
+                    .init(device)
+            })
+            .collect();
+
+        let n_features = if self.conv_layers == 0 {
+            self.n_channels * self.len
+        } else {
+            self.conv_filters * self.len
+        };
+        let hidden = (0..self.hidden_layers)
+            .map(|layer| {
+                let size_in = if layer == 0 {
+                    n_features
+                } else {
+                    self.hidden_size
+                };
+
+                LinearConfig::new(size_in, self.hidden_size).init(device)
+            })
+            .collect();
+
+        let size_out = if self.hidden_layers == 0 {
+            n_features
+        } else {
+            self.hidden_size
+        };
+
+        Net {
+            convs,
+            hidden,
+            policy: LinearConfig::new(size_out, self.n_colors).init(device),
+            value: LinearConfig::new(size_out, 1).init(device),
+            activation: Relu::new(),
+        }
+    }
+}
+
+impl<B: Backend> Net<B> {
+    /// Logits of the colors and values in `[0, 1]` of observations `[batch, channels, len]`.
+    pub fn forward(&self, observations: Tensor<B, 3>) -> (Tensor<B, 2>, Tensor<B, 1>) {
+        let mut x = observations;
+        for conv in &self.convs {
+            x = self.activation.forward(conv.forward(x));
+        }
+
+        let mut x = x.flatten::<2>(1, 2);
+        for layer in &self.hidden {
+            x = self.activation.forward(layer.forward(x));
+        }
+
+        let logits = self.policy.forward(x.clone());
+        let values = sigmoid(self.value.forward(x)).squeeze(1);
+
+        (logits, values)
+    }
+}
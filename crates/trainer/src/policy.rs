@@ -0,0 +1,98 @@
+use crate::{model::Net, train::load_model};
+use burn::{
+    backend::NdArray,
+    tensor::{Tensor, TensorData},
+};
+use ramsey_theory::{
+    Evaluator, Policy, SequenceColoring, SequenceProblem, observation::observations,
+    policies::legal_softmax,
+};
+use rand::RngCore;
+use std::path::Path;
+
+pub struct Prediction {
+    /// Softmax of the logits over the legal moves.
+    pub distributions: Vec<Vec<f64>>,
+    /// Predicted final sizes over the upper bound.
+    pub values: Vec<f64>,
+}
+
+/// Policy of a network trained by `trainer train`, on CPU.
+///
+/// The model cannot be shared between threads, each thread needs its own clone.
+#[derive(Clone)]
+pub struct TrainedPolicy {
+    model: Net<NdArray>,
+}
+
+impl TrainedPolicy {
+    /// Loads the model of an output directory of `trainer train`.
+    pub fn load<P>(directory: impl AsRef<Path>) -> Result<Self, String>
+    where
+        P: SequenceProblem,
+    {
+        let model = load_model::<P, _>(directory.as_ref(), &Default::default())?;
+
+        Ok(Self { model })
+    }
+
+    pub fn predict<P>(&self, colorings: &[SequenceColoring<P>]) -> Prediction
+    where
+        P: SequenceProblem,
+        [(); P::BOUND]:,
+        [(); P::N_COLORS]:,
+    {
+        let observations = observations(colorings);
+        let input = TensorData::new(
+            observations.iter().copied().collect(),
+            observations.shape().to_vec(),
+        );
+
+        let (logits, values) = self
+            .model
+            .forward(Tensor::from_data(input, &Default::default()));
+        let logits = logits.into_data().to_vec::<f32>().unwrap();
+        let values = values.into_data().to_vec::<f32>().unwrap();
+
+        Prediction {
+            distributions: colorings
+                .iter()
+                .zip(logits.chunks(P::N_COLORS))
+                .map(|(coloring, logits)| legal_softmax(coloring, logits))
+                .collect(),
+            values: values.into_iter().map(f64::from).collect(),
+        }
+    }
+}
+
+impl<P> Policy<P> for TrainedPolicy
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    fn distribution(&self, coloring: &SequenceColoring<P>) -> Vec<f64> {
+        self.distributions(std::slice::from_ref(coloring))
+            .pop()
+            .unwrap()
+    }
+
+    fn distributions(&self, colorings: &[SequenceColoring<P>]) -> Vec<Vec<f64>> {
+        self.predict(colorings).distributions
+    }
+}
+
+impl<P> Evaluator<P> for TrainedPolicy
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    fn value(&self, coloring: &SequenceColoring<P>, _rng: &mut dyn RngCore) -> f64 {
+        self.predict(std::slice::from_ref(coloring)).values[0]
+    }
+
+    fn values(&self, colorings: &[SequenceColoring<P>], _rng: &mut dyn RngCore) -> Vec<f64> {
+        self.predict(colorings).values
+    }
+}
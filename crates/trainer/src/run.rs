@@ -0,0 +1,35 @@
+use crate::{
+    cli::{SelfplayArgs, TrainArgs},
+    selfplay::selfplay,
+    train::train,
+};
+use data_generator::Problem;
+use ramsey_theory::problems::{Schur, WeakSchur};
+
+pub type TrainFn = fn(TrainArgs);
+pub type SelfplayFn = fn(SelfplayArgs);
+
+macro_rules! get_fn {
+    ($function:ident, $params:ident, $problem:tt, { $($cases:literal)* }) => {
+        {let fn_array = [$($function::<$problem<$cases>>),*];
+        let cases = [$($cases),*];
+        let index = cases.iter().position(|x| *x == $params).unwrap();
+        fn_array[index]}
+    };
+}
+
+pub fn get_train_fn_pointer(problem: Problem, n_colors: usize) -> TrainFn {
+    match problem {
+        Problem::Schur => get_fn!(train, n_colors, Schur, { 2 3 4 5 }),
+        Problem::WeakSchur => get_fn!(train, n_colors, WeakSchur, { 2 3 4 5 }),
+        _ => unimplemented!(),
+    }
+}
+
+pub fn get_selfplay_fn_pointer(problem: Problem, n_colors: usize) -> SelfplayFn {
+    match problem {
+        Problem::Schur => get_fn!(selfplay, n_colors, Schur, { 2 3 4 5 }),
+        Problem::WeakSchur => get_fn!(selfplay, n_colors, WeakSchur, { 2 3 4 5 }),
+        _ => unimplemented!(),
+    }
+}
@@ -0,0 +1,244 @@
+use crate::{
+    cli::{Cli, SearchKind, SelfplayArgs, TrainArgs, TrainingArgs},
+    policy::TrainedPolicy,
+    train::train,
+};
+use clap::{CommandFactory, error::ErrorKind};
+use data_generator::{Dataset, Mode, Sample, generate_dataset, save_data};
+use ndarray::Array2;
+use ramsey_theory::{
+    CompressedColoring, Evaluator, Policy, SequenceProblem,
+    assert_const_generics::*,
+    policies::UniformRandom,
+    search::{
+        Rollout, SearchGame,
+        mcts::{Mcts, MctsConfig},
+        nmcs::Nmcs,
+    },
+};
+use rand::{SeedableRng, rngs::StdRng};
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+// Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+#[allow(clippy::eq_op)]
+fn play_games<P, Pol, E>(
+    args: &SelfplayArgs,
+    seed: u64,
+    n_workers: usize,
+    policy: &Pol,
+    evaluator: &E,
+) -> Vec<SearchGame<{ P::N_COLORS }>>
+where
+    P: SequenceProblem,
+    Pol: Policy<P> + Clone + Send,
+    E: Evaluator<P> + Clone + Send,
+    Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let next_game = AtomicUsize::new(0);
+    let mut games = vec![None; args.games];
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..n_workers)
+            .map(|_| {
+                let (policy, evaluator, next_game) =
+                    (policy.clone(), evaluator.clone(), &next_game);
+
+                scope.spawn(move || {
+                    let mut played = Vec::new();
+
+                    loop {
+                        let game = next_game.fetch_add(1, Ordering::Relaxed);
+                        if game >= args.games {
+                            break played;
+                        }
+
+                        // Each game has its own seed so the results do not depend on the scheduling.
+                        let mut rng = StdRng::seed_from_u64(seed.wrapping_add(game as u64));
+                        let search_game = match args.search {
+                            SearchKind::Mcts => {
+                                let config = MctsConfig {
+                                    simulations: args.simulations,
+                                    exploration: args.exploration,
+                                    sampled_moves: args.sampled_moves,
+                                };
+                                let mcts = Mcts {
+                                    policy: &policy,
+                                    evaluator: &evaluator,
+                                    config,
+                                };
+                                mcts.play::<P>(&mut rng)
+                            }
+                            SearchKind::Nmcs => {
+                                let nmcs = Nmcs {
+                                    policy: &policy,
+                                    level: args.level,
+                                };
+                                nmcs.play::<P>(&mut rng)
+                            }
+                        };
+                        played.push((game, search_game));
+                    }
+                })
+            })
+            .collect();
+
+        for worker in workers {
+            for (game, search_game) in worker.join().unwrap() {
+                games[game] = Some(search_game);
+            }
+        }
+    });
+
+    games.into_iter().map(Option::unwrap).collect()
+}
+
+/// Every state of the games where the search chose a move, labelled with its distribution.
+fn search_dataset<P>(games: &[SearchGame<{ P::N_COLORS }>]) -> Dataset
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    let mut samples = Vec::new();
+    let mut targets = Vec::new();
+
+    for (game_id, game) in games.iter().enumerate() {
+        for (size, target) in game.targets.iter().enumerate() {
+            samples.push(Sample {
+                coloring: CompressedColoring::from(game.coloring.prefix(size)),
+                game_id,
+                final_size: game.coloring.len(),
+            });
+            targets.extend(target.iter().map(|&probability| probability as f32));
+        }
+    }
+
+    let n_samples = samples.len();
+    let mut dataset = generate_dataset::<P>(samples, Mode::Trajectory, None, false);
+    dataset.policies = Some(Array2::from_shape_vec((n_samples, P::N_COLORS), targets).unwrap());
+
+    dataset
+}
+
+fn error(message: String) -> ! {
+    Cli::command().error(ErrorKind::Io, message).exit()
+}
+
+fn write_coloring(filename: &Path, coloring: &[usize]) {
+    let colors: Vec<_> = coloring.iter().map(usize::to_string).collect();
+
+    fs::write(filename, colors.join(",") + "\n")
+        .unwrap_or_else(|err| error(format!("cannot write {}: {err}", filename.display())));
+}
+
+// Clippy false positive: rustc needs `P::N_COLORS == P::N_COLORS`
+#[allow(clippy::eq_op)]
+pub fn selfplay<P>(args: SelfplayArgs)
+where
+    P: SequenceProblem,
+    Assert<{ P::N_COLORS == P::N_COLORS }>: IsTrue,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    if args.games == 0 || args.window == 0 {
+        Cli::command()
+            .error(
+                ErrorKind::ValueValidation,
+                "at least one game and one iteration in the window are needed",
+            )
+            .exit();
+    }
+
+    let directory = args.output_dir.as_path();
+    fs::create_dir_all(directory)
+        .unwrap_or_else(|err| error(format!("cannot create {}: {err}", directory.display())));
+
+    let history_filename = directory.join("history.csv");
+    let mut history = File::create(&history_filename).unwrap_or_else(|err| {
+        error(format!(
+            "cannot create {}: {err}",
+            history_filename.display()
+        ))
+    });
+    writeln!(history, "iteration,best_size,mean_size").unwrap();
+
+    let n_workers = args
+        .workers
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, Into::into))
+        .max(1);
+
+    let mut model = args.training.init_model.clone();
+    let mut datasets: Vec<PathBuf> = Vec::new();
+
+    for iteration in 0..args.iterations {
+        let iteration_directory = directory.join(format!("iteration-{iteration:03}"));
+        fs::create_dir_all(&iteration_directory).unwrap_or_else(|err| {
+            error(format!(
+                "cannot create {}: {err}",
+                iteration_directory.display()
+            ))
+        });
+
+        let seed = args
+            .training
+            .seed
+            .wrapping_add((iteration * args.games) as u64);
+        let games = match &model {
+            Some(model) => {
+                let policy = TrainedPolicy::load::<P>(model).unwrap_or_else(|err| error(err));
+                play_games::<P, _, _>(&args, seed, n_workers, &policy, &policy)
+            }
+            // Without a model, the priors are uniform and the values come from random games.
+            None => {
+                let evaluator = Rollout {
+                    policy: &UniformRandom,
+                    n_rollouts: 1,
+                };
+                play_games::<P, _, _>(&args, seed, n_workers, &UniformRandom, &evaluator)
+            }
+        };
+
+        let sizes: Vec<_> = games.iter().map(|game| game.coloring.len()).collect();
+        let best_game = games.iter().max_by_key(|game| game.coloring.len()).unwrap();
+        let best_size = best_game.coloring.len();
+        let mean_size = sizes.iter().sum::<usize>() as f64 / sizes.len() as f64;
+
+        println!(
+            "Iteration {}/{}: best size {best_size}, mean size {mean_size:.2} (upper bound {})",
+            iteration + 1,
+            args.iterations,
+            P::BOUND - 1
+        );
+        writeln!(history, "{iteration},{best_size},{mean_size}").unwrap();
+        write_coloring(&iteration_directory.join("best.txt"), &best_game.coloring);
+
+        let dataset_filename = iteration_directory.join("games.npz");
+        save_data(dataset_filename.clone(), search_dataset::<P>(&games));
+        datasets.push(dataset_filename);
+
+        let model_directory = iteration_directory.join("model");
+        let first_dataset = datasets.len().saturating_sub(args.window);
+        train::<P>(TrainArgs {
+            problem: args.problem,
+            colors: args.colors,
+            data: datasets[first_dataset..].to_vec(),
+            output_dir: model_directory.clone(),
+            resume: false,
+            training: TrainingArgs {
+                init_model: model.clone(),
+                seed: args.training.seed.wrapping_add(iteration as u64),
+                ..args.training.clone()
+            },
+            net: args.net.clone(),
+        });
+        model = Some(model_directory);
+    }
+}
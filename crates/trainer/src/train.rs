@@ -0,0 +1,406 @@
+use crate::{
+    cli::{Cli, NetArgs, TrainArgs},
+    data::{TrainingData, load_datasets},
+    model::{Net, NetConfig},
+};
+use burn::{
+    backend::{Autodiff, NdArray},
+    config::Config,
+    module::{AutodiffModule, Module},
+    optim::{Adam, AdamConfig, GradientsParams, Optimizer, adaptor::OptimizerAdaptor},
+    record::{FullPrecisionSettings, NamedMpkFileRecorder, Recorder},
+    tensor::{ElementConversion, Tensor, TensorData, activation::log_softmax, backend::Backend},
+};
+use clap::{CommandFactory, error::ErrorKind};
+use ramsey_theory::{SequenceProblem, observation::shape};
+use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
+use std::{fs, path::Path};
+
+type TrainBackend = Autodiff<NdArray>;
+type TrainOptimizer = OptimizerAdaptor<Adam, Net<TrainBackend>, TrainBackend>;
+
+const CONFIG_FILE: &str = "config.json";
+const MODEL_FILE: &str = "model";
+const CHECKPOINT_FILE: &str = "checkpoint.json";
+const CHECKPOINT_MODEL_FILE: &str = "checkpoint-model";
+const CHECKPOINT_OPTIMIZER_FILE: &str = "checkpoint-optimizer";
+
+fn recorder() -> NamedMpkFileRecorder<FullPrecisionSettings> {
+    NamedMpkFileRecorder::new()
+}
+
+#[derive(Config, Debug)]
+pub struct TrainingConfig {
+    pub net: NetConfig,
+    pub batch_size: usize,
+    pub learning_rate: f64,
+    pub value_weight: f64,
+    pub validation: f64,
+    pub seed: u64,
+}
+
+/// Last epoch saved in the output directory.
+#[derive(Config, Debug)]
+struct Checkpoint {
+    epoch: usize,
+}
+
+fn net_config<P>(args: &NetArgs) -> NetConfig
+where
+    P: SequenceProblem,
+{
+    let (n_channels, len) = shape::<P>();
+
+    NetConfig::new(n_channels, len, P::N_COLORS)
+        .with_conv_layers(args.conv_layers)
+        .with_conv_filters(args.conv_filters)
+        .with_kernel_size(args.kernel_size)
+        .with_hidden_layers(args.hidden_layers)
+        .with_hidden_size(args.hidden_size)
+}
+
+struct Batch<B: Backend> {
+    observations: Tensor<B, 3>,
+    legal_moves: Tensor<B, 2>,
+    values: Option<Tensor<B, 1>>,
+    policies: Option<Tensor<B, 2>>,
+}
+
+impl<B: Backend> Batch<B> {
+    fn new(data: &TrainingData, indices: &[usize], device: &B::Device) -> Self {
+        let data = data.select(indices);
+
+        let observations = TensorData::new(
+            data.observations.iter().copied().collect(),
+            data.observations.shape().to_vec(),
+        );
+        let legal_moves = TensorData::new(
+            data.legal_moves.iter().copied().collect(),
+            data.legal_moves.shape().to_vec(),
+        );
+        let policies = data.policies.map(|policies| {
+            TensorData::new(
+                policies.iter().copied().collect(),
+                policies.shape().to_vec(),
+            )
+        });
+
+        Self {
+            observations: Tensor::from_data(observations, device),
+            legal_moves: Tensor::from_data(legal_moves, device),
+            values: data.values.map(|values| {
+                let values = TensorData::new(values.to_vec(), [values.len()]);
+                Tensor::from_data(values, device)
+            }),
+            policies: policies.map(|policies| Tensor::from_data(policies, device)),
+        }
+    }
+}
+
+struct Losses<B: Backend> {
+    policy: Tensor<B, 1>,
+    value: Option<Tensor<B, 1>>,
+    /// Fraction of the colors whose legality is predicted right, or with the targets of a
+    /// search, fraction of the samples whose most likely move is the one of the search.
+    accuracy: Tensor<B, 1>,
+}
+
+impl<B: Backend> Losses<B> {
+    fn new(model: &Net<B>, batch: Batch<B>) -> Self {
+        let (logits, values) = model.forward(batch.observations);
+
+        let (policy, accuracy) = match batch.policies {
+            Some(targets) => {
+                // Cross-entropy of the softmax over the legal moves, as in the inference.
+                let illegal = batch.legal_moves.lower_elem(0.5);
+                let logits = logits.mask_fill(illegal, -1e9);
+
+                let accuracy = logits
+                    .clone()
+                    .argmax(1)
+                    .equal(targets.clone().argmax(1))
+                    .float()
+                    .mean();
+                let policy = -(log_softmax(logits, 1) * targets).sum_dim(1).mean();
+
+                (policy, accuracy)
+            }
+            None => {
+                let accuracy = logits
+                    .clone()
+                    .greater_elem(0.0)
+                    .equal(batch.legal_moves.clone().greater_elem(0.5))
+                    .float()
+                    .mean();
+
+                // Binary cross-entropy with logits, stable for the large logits.
+                let policy = logits.clone().clamp_min(0.0) - logits.clone() * batch.legal_moves
+                    + logits.abs().neg().exp().log1p();
+
+                (policy.mean(), accuracy)
+            }
+        };
+        let value = batch
+            .values
+            .map(|targets| (values - targets).powi_scalar(2).mean());
+
+        Self {
+            policy,
+            value,
+            accuracy,
+        }
+    }
+
+    fn total(&self, value_weight: f64) -> Tensor<B, 1> {
+        match &self.value {
+            Some(value) => self.policy.clone() + value.clone() * value_weight,
+            None => self.policy.clone(),
+        }
+    }
+}
+
+/// Means of the losses over the batches of an epoch.
+#[derive(Default)]
+struct Metrics {
+    n_samples: usize,
+    policy: f64,
+    value: f64,
+    accuracy: f64,
+}
+
+impl Metrics {
+    fn add<B: Backend>(&mut self, losses: &Losses<B>, n_samples: usize) {
+        let scalar = |tensor: &Tensor<B, 1>| -> f64 { tensor.clone().into_scalar().elem() };
+
+        self.n_samples += n_samples;
+        self.policy += scalar(&losses.policy) * n_samples as f64;
+        self.value += losses.value.as_ref().map_or(0.0, scalar) * n_samples as f64;
+        self.accuracy += scalar(&losses.accuracy) * n_samples as f64;
+    }
+
+    fn report(&self, with_value: bool) -> String {
+        let n_samples = self.n_samples as f64;
+        let mut report = format!(
+            "policy loss {:.4}, accuracy {:.4}",
+            self.policy / n_samples,
+            self.accuracy / n_samples
+        );
+        if with_value {
+            report += &format!(", value loss {:.4}", self.value / n_samples);
+        }
+
+        report
+    }
+}
+
+/// Configuration of an output directory, checked against the problem.
+fn load_config<P>(directory: &Path) -> Result<TrainingConfig, String>
+where
+    P: SequenceProblem,
+{
+    let config = TrainingConfig::load(directory.join(CONFIG_FILE)).map_err(|err| {
+        format!(
+            "cannot read the configuration of {}: {err}",
+            directory.display()
+        )
+    })?;
+
+    if config.net.n_colors != P::N_COLORS || (config.net.n_channels, config.net.len) != shape::<P>()
+    {
+        return Err(format!(
+            "{} is not a model of this problem",
+            directory.display()
+        ));
+    }
+
+    Ok(config)
+}
+
+/// Trained model of an output directory.
+pub fn load_model<P, B>(directory: &Path, device: &B::Device) -> Result<Net<B>, String>
+where
+    P: SequenceProblem,
+    B: Backend,
+{
+    load_config::<P>(directory)?
+        .net
+        .init(device)
+        .load_file(directory.join(MODEL_FILE), &recorder(), device)
+        .map_err(|err| format!("cannot read the model of {}: {err}", directory.display()))
+}
+
+fn error(message: String) -> ! {
+    Cli::command().error(ErrorKind::Io, message).exit()
+}
+
+fn load_checkpoint(
+    directory: &Path,
+    config: &TrainingConfig,
+) -> Result<(usize, Net<TrainBackend>, TrainOptimizer), String> {
+    let device = Default::default();
+    let recorder = recorder();
+
+    let checkpoint = Checkpoint::load(directory.join(CHECKPOINT_FILE))
+        .map_err(|err| format!("cannot read the checkpoint: {err}"))?;
+    let model = config
+        .net
+        .init::<TrainBackend>(&device)
+        .load_file(directory.join(CHECKPOINT_MODEL_FILE), &recorder, &device)
+        .map_err(|err| format!("cannot read the checkpoint model: {err}"))?;
+    let optimizer_record = recorder
+        .load(
+            directory
+                .join(CHECKPOINT_OPTIMIZER_FILE)
+                .with_extension("mpk"),
+            &device,
+        )
+        .map_err(|err| format!("cannot read the checkpoint optimizer: {err}"))?;
+    let optimizer = AdamConfig::new().init().load_record(optimizer_record);
+
+    Ok((checkpoint.epoch + 1, model, optimizer))
+}
+
+pub fn train<P>(args: TrainArgs)
+where
+    P: SequenceProblem,
+    [(); P::BOUND]:,
+    [(); P::N_COLORS]:,
+{
+    if args.net.conv_layers > 0 && args.net.kernel_size % 2 == 0 {
+        Cli::command()
+            .error(ErrorKind::ValueValidation, "the kernel size must be odd")
+            .exit();
+    }
+    if !(0.0..1.0).contains(&args.training.validation) {
+        Cli::command()
+            .error(
+                ErrorKind::ValueValidation,
+                "the validation fraction must be in [0, 1)",
+            )
+            .exit();
+    }
+
+    let directory = args.output_dir.as_path();
+    let device = Default::default();
+
+    let training = &args.training;
+    let config = if args.resume {
+        // The architecture of the checkpoint wins over the arguments.
+        load_config::<P>(directory).unwrap_or_else(|err| error(err))
+    } else {
+        // So does the architecture of the initial model.
+        let net = match &training.init_model {
+            Some(init_model) => {
+                load_config::<P>(init_model)
+                    .unwrap_or_else(|err| error(err))
+                    .net
+            }
+            None => net_config::<P>(&args.net),
+        };
+
+        TrainingConfig::new(
+            net,
+            training.batch_size,
+            training.learning_rate,
+            training.value_weight,
+            training.validation,
+            training.seed,
+        )
+    };
+
+    let data = load_datasets::<P>(&args.data)
+        .unwrap_or_else(|err| Cli::command().error(ErrorKind::InvalidValue, err).exit());
+    if data.is_empty() {
+        Cli::command()
+            .error(ErrorKind::InvalidValue, "the datasets are empty")
+            .exit();
+    }
+    if data.values.is_none() {
+        eprintln!("Some datasets have no final sizes, the value head is not trained");
+    }
+
+    let mut indices: Vec<_> = (0..data.len()).collect();
+    indices.shuffle(&mut StdRng::seed_from_u64(config.seed));
+    let n_validation = (data.len() as f64 * config.validation) as usize;
+    let (validation_indices, train_indices) = indices.split_at(n_validation);
+    let validation = data.select(validation_indices);
+    let data = data.select(train_indices);
+    let validation_indices: Vec<_> = (0..validation.len()).collect();
+    let with_value = data.values.is_some();
+
+    TrainBackend::seed(config.seed);
+    let (first_epoch, mut model, mut optimizer) = if args.resume {
+        load_checkpoint(directory, &config).unwrap_or_else(|err| error(err))
+    } else {
+        let model = match &training.init_model {
+            Some(init_model) => {
+                load_model::<P, TrainBackend>(init_model, &device).unwrap_or_else(|err| error(err))
+            }
+            None => config.net.init(&device),
+        };
+        (0, model, AdamConfig::new().init())
+    };
+
+    fs::create_dir_all(directory)
+        .unwrap_or_else(|err| error(format!("cannot create {}: {err}", directory.display())));
+    config
+        .save(directory.join(CONFIG_FILE))
+        .unwrap_or_else(|err| error(format!("cannot write the configuration: {err}")));
+
+    let recorder = recorder();
+    let mut train_indices: Vec<_> = (0..data.len()).collect();
+
+    for epoch in first_epoch..training.epochs {
+        // Each epoch has its own seed so that a resumed training shuffles the same way.
+        let mut rng = StdRng::seed_from_u64(config.seed.wrapping_add(epoch as u64 + 1));
+        train_indices.sort_unstable();
+        train_indices.shuffle(&mut rng);
+
+        let mut metrics = Metrics::default();
+        for batch in train_indices.chunks(config.batch_size) {
+            let losses = Losses::new(&model, Batch::new(&data, batch, &device));
+            metrics.add(&losses, batch.len());
+
+            let grads =
+                GradientsParams::from_grads(losses.total(config.value_weight).backward(), &model);
+            model = optimizer.step(config.learning_rate, model, grads);
+        }
+        println!(
+            "Epoch {}/{}: {}",
+            epoch + 1,
+            training.epochs,
+            metrics.report(with_value)
+        );
+
+        if !validation.is_empty() {
+            let valid_model = model.valid();
+            let mut metrics = Metrics::default();
+            for batch in validation_indices.chunks(config.batch_size) {
+                let losses = Losses::new(&valid_model, Batch::new(&validation, batch, &device));
+                metrics.add(&losses, batch.len());
+            }
+            println!("    validation: {}", metrics.report(with_value));
+        }
+
+        model
+            .clone()
+            .save_file(directory.join(CHECKPOINT_MODEL_FILE), &recorder)
+            .and_then(|()| {
+                recorder.record(
+                    optimizer.to_record(),
+                    directory
+                        .join(CHECKPOINT_OPTIMIZER_FILE)
+                        .with_extension("mpk"),
+                )
+            })
+            .unwrap_or_else(|err| error(format!("cannot write the checkpoint: {err}")));
+        Checkpoint::new(epoch)
+            .save(directory.join(CHECKPOINT_FILE))
+            .unwrap_or_else(|err| error(format!("cannot write the checkpoint: {err}")));
+    }
+
+    model
+        .valid()
+        .save_file(directory.join(MODEL_FILE), &recorder)
+        .unwrap_or_else(|err| error(format!("cannot write the model: {err}")));
+}